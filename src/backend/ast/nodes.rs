@@ -1,5 +1,5 @@
 use crate::backend::compiler::byte_code::Compilable;
-use crate::backend::lexer::tokens::TokenKind;
+use crate::backend::lexer::tokens::{Span, TokenKind};
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 
@@ -12,12 +12,14 @@ pub enum CallType {
 #[derive(Clone)]
 pub struct ProgramNode {
     pub program_nodes: Vec<Box<dyn Compilable>>,
+    pub span: Span,
 }
 
 impl ProgramNode {
     pub fn new() -> Self {
         Self {
             program_nodes: Vec::new(),
+            span: Span::default(),
         }
     }
 }
@@ -40,6 +42,7 @@ pub struct BinaryOpNode {
     pub left: Box<dyn Compilable>,
     pub right: Box<dyn Compilable>,
     pub op_tok: TokenKind,
+    pub span: Span,
 }
 
 impl fmt::Debug for BinaryOpNode {
@@ -53,7 +56,8 @@ impl fmt::Debug for BinaryOpNode {
 #[derive(Clone)]
 pub struct PrefixExpressionNode{
     pub prefix:TokenKind,
-    pub value:Box<dyn Compilable>
+    pub value:Box<dyn Compilable>,
+    pub span: Span,
 }
 
 
@@ -63,6 +67,7 @@ Number Node
 #[derive(Clone)]
 pub struct NumberNode {
     pub number: i64,
+    pub span: Span,
 }
 
 impl fmt::Debug for NumberNode {
@@ -76,6 +81,7 @@ Float node
 #[derive(Clone)]
 pub struct FloatNode {
     pub number: f32,
+    pub span: Span,
 }
 
 impl fmt::Debug for FloatNode {
@@ -90,36 +96,142 @@ impl fmt::Debug for FloatNode {
 #[derive(Clone)]
 pub struct StringNode {
     pub value: String,
+    pub span: Span,
 }
 impl Debug for StringNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.fmt_with_indent(f, 0)
     }
 }
+/*
+ * Expression statement node
+ */
+/// An expression used as a statement, like a function call on its own line.
+#[derive(Clone)]
+pub struct ExpressionStatementNode {
+    pub expr: Box<dyn Compilable>,
+}
+impl Debug for ExpressionStatementNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+/*
+ * Interpolated string node
+ */
+#[derive(Clone, Debug)]
+pub enum InterpolationPart {
+    Text(String),
+    Expr(Box<dyn Compilable>),
+}
+
+#[derive(Clone)]
+pub struct InterpolatedStringNode {
+    pub parts: Vec<InterpolationPart>,
+    pub span: Span,
+}
+impl Debug for InterpolatedStringNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
 /*
  * Bool node
  */
 #[derive(Clone)]
 pub struct BoolNode {
     pub value: TokenKind,
+    pub span: Span,
 }
 impl Debug for BoolNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.fmt_with_indent(f, 0)
     }
 }
+/*
+ * Undef node
+ */
+#[derive(Clone)]
+pub struct UndefNode {
+    pub span: Span,
+}
+impl Debug for UndefNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
 /*
  * Array node
  */
 #[derive(Clone)]
 pub struct ArrayNode {
     pub elements: Vec<Box<dyn Compilable>>,
+    pub span: Span,
 }
+
 impl Debug for ArrayNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.fmt_with_indent(f, 0)
     }
 }
+
+/*
+ * Map node
+ */
+#[derive(Clone)]
+pub struct MapNode {
+    /// Keys and values in the order they are written
+    pub entries: Vec<(Box<dyn Compilable>, Box<dyn Compilable>)>,
+    pub span: Span,
+}
+impl Debug for MapNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+/*
+ * Tuple node
+ */
+#[derive(Clone)]
+pub struct TupleNode {
+    pub elements: Vec<Box<dyn Compilable>>,
+    pub span: Span,
+}
+impl Debug for TupleNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+/*
+ * Index node
+ */
+#[derive(Clone)]
+pub struct IndexNode {
+    pub object: Box<dyn Compilable>,
+    pub index: Box<dyn Compilable>,
+    pub span: Span,
+}
+impl Debug for IndexNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+/*
+ * Assignment to a field or element, like `p.x = v` or `a[i] = v`
+ */
+#[derive(Clone)]
+pub struct PathAssignNode {
+    /// Expression being assigned to, checked with `assign_target` when compiling
+    pub target: Box<dyn Compilable>,
+    pub value: Box<dyn Compilable>,
+    pub span: Span,
+}
+impl Debug for PathAssignNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
 /*
 Variable Access
 */
@@ -127,6 +239,7 @@ Variable Access
 #[derive(Clone)]
 pub struct VariableAccessNode {
     pub variable_name: String,
+    pub span: Span,
 }
 
 impl fmt::Debug for VariableAccessNode {
@@ -139,10 +252,32 @@ Variable Define
 */
 #[derive(Clone)]
 pub struct VariableDefineNode {
-    pub var_name: String,
+    pub pattern: BindingPattern,
     pub value_type: Option<String>,
     pub value: Option<Box<dyn Compilable>>,
     pub is_const: bool,
+    pub span: Span,
+    /// `///` documentation written above the declaration.
+    pub doc: Option<String>,
+}
+/// What a `var` declaration binds its value to.
+#[derive(Clone, Debug)]
+pub enum BindingPattern {
+    /// A single variable, `_` throws the value away
+    Name(String),
+    /// `(q, r)`, binds every element of a tuple to its own pattern
+    Tuple(Vec<BindingPattern>),
+}
+impl fmt::Display for BindingPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingPattern::Name(name) => write!(f, "{}", name),
+            BindingPattern::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+        }
+    }
 }
 impl Debug for VariableDefineNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -157,6 +292,7 @@ Variable assign node
 pub struct VariableAssignNode {
     pub name: String,
     pub value: Box<dyn Compilable>,
+    pub span: Span,
 }
 
 impl Debug for VariableAssignNode {
@@ -172,8 +308,18 @@ FunctionCallNode
 #[derive(Clone)]
 pub struct FunctionCallNode {
     pub args: Vec<Box<dyn Compilable>>,
+    /// `name = value` arguments, always written after the positional ones
+    pub named_args: Vec<NamedArgument>,
     pub name: String,
     pub call_type: CallType,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct NamedArgument {
+    pub name: String,
+    pub value: Box<dyn Compilable>,
+    pub span: Span,
 }
 
 impl Debug for FunctionCallNode {
@@ -1,10 +1,13 @@
 use crate::backend::{
-    ast::{nodes::{BinaryOpNode, BoolNode, CallType::{Fn, Macro}, FloatNode, FunctionCallNode, NumberNode, ProgramNode, StringNode, VariableAccessNode, VariableAssignNode, VariableDefineNode}, statements::{functions::{args_node::FunctionArgs, function_nodes::FunctionDefineNode}, if_statement::IfStatement, while_statement::WhileStatement}},
+    ast::{nodes::{ArrayNode, BinaryOpNode, BoolNode, CallType::{Fn, Macro}, ExpressionStatementNode, FloatNode, FunctionCallNode, IndexNode, InterpolatedStringNode, NamedArgument, PathAssignNode, InterpolationPart, MapNode, NumberNode, TupleNode, BindingPattern, PrefixExpressionNode, ProgramNode, StringNode, UndefNode, VariableAccessNode, VariableAssignNode, VariableDefineNode}, statements::{for_statement::ForStatement, functions::{args_node::{FunctionArgs, TypeParam}, closures::{CallValueNode, LambdaBody, LambdaNode}, function_nodes::FunctionDefineNode, return_statement::ReturnStatement}, if_statement::{IfBranch, IfStatement}, loop_statement::{BreakStatement, ContinueStatement, LoopStatement}, enums::{EnumDefineNode, EnumVariant, IsVariantNode}, match_statement::{MatchArm, MatchPattern, MatchStatement}, impl_node::{ImplNode, MethodCallNode}, structs::{FieldAccessNode, StructDefineNode, StructFieldValue, StructLiteralNode, StructVariable}, try_statement::{ThrowStatement, TryStatement}, while_statement::WhileStatement}},
     compiler::byte_code::Compilable,
     errors::parser_errors::ParserError::{self, UnexpectedToken},
-    lexer::tokens::{Token, TokenKind::{self, CLOSINGBRACE, COLON, COMMA, CONST, DIVIDE, ELSE, EOF, EQUAL, FALSE, FLOAT, FNC, GREATER, IDENTIFIER, IF, LEFTPAREN, LESS, MINUS, MODULO, NUMB, OPENINGBRACE, PLUS, RIGHTPAREN, SEMICOLON, STRING, TIMES, TRUE, VALUE, VAR, WHILE}},
+    lexer::tokens::{Span, Token, TokenKind::{self, AND, BREAK, CLOSINGBRACE, CONTINUE, DOTDOT, DOTDOTEQUAL, COLON, COMMA, CONST, DIVIDE, DOT, ELSE, ENUM, EOF, EQUAL, EQUALEQUAL, FALSE, FATARROW, FLOAT, FNC, FOR, GREATER, GREATEREQUAL, IDENTIFIER, IF, IMPL, IN, IS, LEFTBRACKET, INTERPOLATIONEND, INTERPOLATIONSTART, LABEL, LEFTPAREN, LESS, LESSEQUAL, LOOP, MATCH, MINUS, MODULO, NOT, NOTEQUAL, NUMB, OR, OPENINGBRACE, PIPE, PLUS, QUESTION, RETURN, CATCH, THROW, TRY, RIGHTBRACKET, RIGHTPAREN, SEMICOLON, STEP, STRING, STRUCT, TIMES, TRUE, UNDEF, VALUE, VAR, WHILE}},
 };
 
+/// Positional and named arguments of a call.
+type CallArguments = (Vec<Box<dyn Compilable>>, Vec<NamedArgument>);
+
 pub struct Parser {
     tokens: Vec<Token>,
     token_idx: usize,
@@ -27,199 +30,686 @@ impl Parser {
 
     pub fn parse(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
         let mut program: ProgramNode = ProgramNode::new();
+        program.span = self.current_token().span.clone();
         while self.current_token().token_kind != EOF {
             program.program_nodes.push(self.parse_stmt()?)
         }
+        program.span = program.span.to(&self.current_token().span);
         Ok(Box::new(program))
     }
 
     fn parse_stmt(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let start = self.current_token().span.clone();
         match &self.current_token().token_kind {
             VAR | CONST => {
-                let value = self.parse_var_decl_stmt();
+                let value = self.parse_var_decl_stmt()?;
                 self.expect(SEMICOLON)?;
-                value
+                Ok(value)
             }
             IDENTIFIER if self.peek() == EQUAL => {
                 let id = self.current_token().token_value.clone();
                 self.advance();
                 self.expect(EQUAL)?;
                 let value = self.parse_expr()?;
+                let span = start.to(value.span());
                 self.expect(SEMICOLON)?;
-                Ok(Box::new(VariableAssignNode { name: id, value }))
+                Ok(Box::new(VariableAssignNode { name: id, value, span }))
             }
             IF => {
                 self.advance();
-                self.expect(LEFTPAREN)?;
-                let condition = self.parse_expr()?;
-                self.expect(RIGHTPAREN)?;
-                self.expect(OPENINGBRACE)?;
-                let mut body: Vec<Box<dyn Compilable>> = Vec::new();
-                while self.current_token().token_kind != CLOSINGBRACE {
-                    if self.current_token().token_kind == EOF {
-                        return Err(ParserError::UnexpectedToken {
-                            found: "EOF".into(),
-                            expected: SEMICOLON,
-                        });
-                    }
-                    body.push(self.parse_stmt()?);
-                }
-                self.expect(CLOSINGBRACE)?;
-                if self.current_token().token_kind == ELSE {
+                let IfBranch {
+                    condition,
+                    body: then_branch,
+                    ..
+                } = self.parse_if_arm(&start)?;
+                let mut else_if_branches = Vec::new();
+                let mut else_branch = None;
+                while self.current_token().token_kind == ELSE {
                     self.advance();
-                    self.expect(OPENINGBRACE)?;
-                    let mut else_body: Vec<Box<dyn Compilable>> = Vec::new();
-                    while self.current_token().token_kind != CLOSINGBRACE {
-                        if self.current_token().token_kind == EOF {
-                            return Err(ParserError::UnexpectedToken {
-                                found: "EOF".into(),
-                                expected: SEMICOLON,
-                            });
-                        }
-                        else_body.push(self.parse_stmt()?);
+                    if self.current_token().token_kind == IF {
+                        let arm_start = self.current_token().span.clone();
+                        self.advance();
+                        else_if_branches.push(self.parse_if_arm(&arm_start)?);
+                    } else {
+                        else_branch = Some(self.parse_block()?);
+                        break;
                     }
-                    self.expect(CLOSINGBRACE)?;
-                    return Ok(Box::new(IfStatement {
-                        condition,
-                        then_branch: body,
-                        else_branch: Some(else_body),
-                    }));
                 }
-                return Ok(Box::new(IfStatement {
+                Ok(Box::new(IfStatement {
                     condition,
-                    then_branch: body,
-                    else_branch: None,
-                }));
+                    then_branch,
+                    else_if_branches,
+                    else_branch,
+                    span: start.to(&self.previous_token().span),
+                }))
             }
-            WHILE => {
+            STRUCT => self.parse_struct_define(&start),
+            ENUM => self.parse_enum_define(&start),
+            WHILE => self.parse_while(&start, None),
+            LOOP => self.parse_loop(&start, None),
+            FOR => self.parse_for(&start, None),
+            MATCH => {
                 self.advance();
-                self.expect(LEFTPAREN)?;
-                let condition = self.parse_expr()?;
-                self.expect(RIGHTPAREN)?;
+                let value = self.parse_expr()?;
                 self.expect(OPENINGBRACE)?;
-                let mut body: Vec<Box<dyn Compilable>> = Vec::new();
+                let mut arms = Vec::new();
                 while self.current_token().token_kind != CLOSINGBRACE {
-                    if self.current_token().token_kind == EOF {
-                        return Err(ParserError::UnexpectedToken {
-                            found: "EOF".into(),
-                            expected: SEMICOLON,
-                        });
+                    arms.push(self.parse_match_arm()?);
+                    if self.current_token().token_kind == COMMA {
+                        self.advance();
                     }
-                    body.push(self.parse_stmt()?);
                 }
                 self.expect(CLOSINGBRACE)?;
-                return Ok(Box::new(WhileStatement { condition, body }));
-            }
-            FNC=>{
-                let mut args = Vec::new();
-                self.advance();//FN
-                let id = self.expect(IDENTIFIER)?;
-                self.expect(LEFTPAREN)?;
-                if self.current_token().token_kind!=RIGHTPAREN {
-
-                    loop {
-                        let arg_name = self.expect(IDENTIFIER)?;
-                        self.expect(COLON)?;
-                        let arg_type = self.expect(IDENTIFIER)?;
-
-                        args.push(FunctionArgs {
-                            name: arg_name.token_value,
-                            argument_type: arg_type.token_value,
-                        });
-
-                        if self.current_token().token_kind == COMMA {
-                            self.advance();
-                            continue;
-                        }
-
-                        break;
-                    }
-                    
+                Ok(Box::new(MatchStatement {
+                    value,
+                    arms,
+                    span: start.to(&self.previous_token().span),
+                }))
+            }
+            LABEL => {
+                let label = self.current_token().token_value.clone();
+                self.advance();
+                self.expect(COLON)?;
+                match self.current_token().token_kind {
+                    WHILE => self.parse_while(&start, Some(label)),
+                    FOR => self.parse_for(&start, Some(label)),
+                    _ => self.parse_loop(&start, Some(label)),
                 }
-                self.expect(RIGHTPAREN)?;
-                let reurn_type = if self.current_token().token_kind == COLON {
+            }
+            BREAK | CONTINUE => {
+                let is_break = self.current_token().token_kind == BREAK;
+                self.advance();
+                let mut label = None;
+                if self.current_token().token_kind == LABEL {
+                    label = Some(self.current_token().token_value.clone());
                     self.advance();
-                    Some(self.expect(IDENTIFIER)?.token_value)
-                }else {
+                }
+                let span = start.to(&self.previous_token().span);
+                self.expect(SEMICOLON)?;
+                if is_break {
+                    Ok(Box::new(BreakStatement { label, span }))
+                } else {
+                    Ok(Box::new(ContinueStatement { label, span }))
+                }
+            }
+            FNC => Ok(Box::new(self.parse_function(&start, None)?)),
+            IMPL => {
+                self.advance();
+                let type_name = self.expect(IDENTIFIER)?.token_value;
+                self.expect(OPENINGBRACE)?;
+                let mut methods = Vec::new();
+                while self.current_token().token_kind != CLOSINGBRACE {
+                    let fnc_start = self.current_token().span.clone();
+                    methods.push(self.parse_function(&fnc_start, Some(&type_name))?);
+                }
+                self.expect(CLOSINGBRACE)?;
+                Ok(Box::new(ImplNode {
+                    type_name,
+                    methods,
+                    span: start.to(&self.previous_token().span),
+                }))
+            }
+            RETURN => {
+                self.advance();
+                let value = if self.current_token().token_kind == SEMICOLON {
                     None
+                } else {
+                    Some(self.parse_expr()?)
                 };
-                self.expect(OPENINGBRACE)?;
+                let span = start.to(&self.previous_token().span);
+                self.expect(SEMICOLON)?;
+                Ok(Box::new(ReturnStatement { value, span }))
+            }
+            TRY => {
+                self.advance();
+                let try_body = self.parse_block()?;
+                self.expect(CATCH)?;
+                let mut error_name = None;
+                if self.current_token().token_kind == LEFTPAREN {
+                    self.advance();
+                    error_name = Some(self.expect(IDENTIFIER)?.token_value);
+                    self.expect(RIGHTPAREN)?;
+                }
+                let catch_body = self.parse_block()?;
+                Ok(Box::new(TryStatement {
+                    try_body,
+                    error_name,
+                    catch_body,
+                    span: start.to(&self.previous_token().span),
+                }))
+            }
+            THROW => {
+                self.advance();
+                let value = self.parse_expr()?;
+                let span = start.to(value.span());
+                self.expect(SEMICOLON)?;
+                Ok(Box::new(ThrowStatement { value, span }))
+            }
+            _ => {
+                let expr = self.parse_expr()?;
+                if self.current_token().token_kind != EQUAL {
+                    return Ok(Box::new(ExpressionStatementNode { expr }));
+                }
+                // `p.x = v;` or `a[i] = v;`
+                self.advance();
+                let value = self.parse_expr()?;
+                let span = start.to(value.span());
+                self.expect(SEMICOLON)?;
+                Ok(Box::new(PathAssignNode {
+                    target: expr,
+                    value,
+                    span,
+                }))
+            }
+        }
+    }
+
+    /// Parses a `fnc` definition, `owner` is the type of the surrounding `impl` block.
+    fn parse_function(
+        &mut self,
+        start: &Span,
+        owner: Option<&str>,
+    ) -> Result<FunctionDefineNode, ParserError> {
+        let mut args = Vec::new();
+        let doc = self.current_token().doc_comment.clone();
+        self.expect(FNC)?;
+        let id = self.expect(IDENTIFIER)?;
+        // Methods can't be generic
+        let type_params = if owner.is_none() && self.current_token().token_kind == LESS {
+            self.parse_type_params()?
+        } else {
+            Vec::new()
+        };
+        self.expect(LEFTPAREN)?;
+        if self.current_token().token_kind != RIGHTPAREN {
+            loop {
+                let arg_name = self.expect(IDENTIFIER)?;
+                // Methods take their receiver as a plain `self`
+                if let Some(owner) = owner
+                    && args.is_empty()
+                    && arg_name.token_value == "self"
+                    && self.current_token().token_kind != COLON
+                {
+                    args.push(FunctionArgs {
+                        span: arg_name.span,
+                        name: arg_name.token_value,
+                        argument_type: owner.to_string(),
+                        default: None,
+                    });
+                } else {
+                    self.expect(COLON)?;
+                    let arg_type = self.parse_type()?;
+                    let default = if self.current_token().token_kind == EQUAL {
+                        self.advance();
+                        Some(self.parse_expr()?)
+                    } else {
+                        None
+                    };
 
-                let mut body:Vec<Box<dyn Compilable>> = Vec::new();
-                while self.current_token().token_kind!=CLOSINGBRACE {
-                    body.push(self.parse_stmt()?); 
+                    args.push(FunctionArgs {
+                        span: arg_name.span.to(&self.previous_token().span),
+                        name: arg_name.token_value,
+                        argument_type: arg_type.token_value,
+                        default,
+                    });
                 }
-                self.expect(CLOSINGBRACE)?;
-                println!("{:?}",&reurn_type);
 
-                Ok(Box::new(FunctionDefineNode{
-                    id:id.token_value,
-                    return_type:reurn_type,
-                    body,
-                    args
+                if self.current_token().token_kind == COMMA {
+                    self.advance();
+                    continue;
+                }
 
-                }))
+                break;
+            }
+        }
+        self.expect(RIGHTPAREN)?;
+        let return_type = if self.current_token().token_kind == COLON {
+            self.advance();
+            Some(self.parse_type()?.token_value)
+        } else {
+            None
+        };
+        let span = start.to(&self.previous_token().span);
+        self.expect(OPENINGBRACE)?;
+
+        let mut body: Vec<Box<dyn Compilable>> = Vec::new();
+        while self.current_token().token_kind != CLOSINGBRACE {
+            body.push(self.parse_stmt()?);
+        }
+        self.expect(CLOSINGBRACE)?;
 
+        Ok(FunctionDefineNode {
+            id: id.token_value,
+            return_type,
+            body,
+            args,
+            span,
+            doc,
+            owner: owner.map(str::to_string),
+            type_params,
+        })
+    }
 
+    /// Parses `<T, U: numeric>` after the name of a generic function.
+    fn parse_type_params(&mut self) -> Result<Vec<TypeParam>, ParserError> {
+        self.expect(LESS)?;
+        let mut type_params = Vec::new();
+        loop {
+            let name = self.expect(IDENTIFIER)?;
+            let constraint = if self.current_token().token_kind == COLON {
+                self.advance();
+                Some(self.expect(IDENTIFIER)?.token_value)
+            } else {
+                None
+            };
+            type_params.push(TypeParam {
+                span: name.span.to(&self.previous_token().span),
+                name: name.token_value,
+                constraint,
+            });
+            if self.current_token().token_kind != COMMA {
+                break;
             }
-            _ => self.parse_expr(),
+            self.advance();
         }
+        self.expect(GREATER)?;
+        Ok(type_params)
     }
 
-    fn parse_var_decl_stmt(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
-        let is_const: bool;
-        if self.current_token().token_kind == CONST {
-            is_const = true;
-        } else {
-            is_const = false;
+    /// Parses `{ field: type, ... }` after `struct Name`.
+    fn parse_struct_define(&mut self, start: &Span) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(STRUCT)?;
+        let name = self.expect(IDENTIFIER)?.token_value;
+        self.expect(OPENINGBRACE)?;
+        let mut args = Vec::new();
+        while self.current_token().token_kind != CLOSINGBRACE {
+            let field = self.expect(IDENTIFIER)?;
+            self.expect(COLON)?;
+            let field_type = self.parse_type()?;
+            args.push(StructVariable {
+                span: field.span.to(&field_type.span),
+                name: field.token_value,
+                var_type: field_type.token_value,
+            });
+            if self.current_token().token_kind != COMMA {
+                break;
+            }
+            self.advance();
         }
-        let id: String;
+        self.expect(CLOSINGBRACE)?;
+        Ok(Box::new(StructDefineNode {
+            name,
+            args,
+            span: start.to(&self.previous_token().span),
+        }))
+    }
+
+    /// Parses `{ Variant(type, ...), ... }` after `enum Name`, with at least one variant.
+    fn parse_enum_define(&mut self, start: &Span) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(ENUM)?;
+        let name = self.expect(IDENTIFIER)?.token_value;
+        self.expect(OPENINGBRACE)?;
+        let mut variants = Vec::new();
+        loop {
+            let variant = self.expect(IDENTIFIER)?;
+            let mut payload = Vec::new();
+            if self.current_token().token_kind == LEFTPAREN {
+                self.advance();
+                while self.current_token().token_kind != RIGHTPAREN {
+                    payload.push(self.parse_type()?.token_value);
+                    if self.current_token().token_kind != COMMA {
+                        break;
+                    }
+                    self.advance();
+                }
+                self.expect(RIGHTPAREN)?;
+            }
+            variants.push(EnumVariant {
+                name: variant.token_value,
+                payload,
+                span: variant.span.to(&self.previous_token().span),
+            });
+            if self.current_token().token_kind != COMMA {
+                break;
+            }
+            self.advance();
+            if self.current_token().token_kind == CLOSINGBRACE {
+                break;
+            }
+        }
+        self.expect(CLOSINGBRACE)?;
+        Ok(Box::new(EnumDefineNode {
+            name,
+            variants,
+            span: start.to(&self.previous_token().span),
+        }))
+    }
+
+    /// Parses `{ field: value, ... }` after the name of a struct.
+    fn parse_struct_literal(
+        &mut self,
+        name: String,
+        start: Span,
+    ) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(OPENINGBRACE)?;
+        let mut fields = Vec::new();
+        while self.current_token().token_kind != CLOSINGBRACE {
+            let field = self.expect(IDENTIFIER)?;
+            self.expect(COLON)?;
+            let value = self.parse_expr()?;
+            fields.push(StructFieldValue {
+                span: field.span.to(value.span()),
+                name: field.token_value,
+                value,
+            });
+            if self.current_token().token_kind != COMMA {
+                break;
+            }
+            self.advance();
+        }
+        let closing = self.expect(CLOSINGBRACE)?;
+        Ok(Box::new(StructLiteralNode {
+            name,
+            fields,
+            span: start.to(&closing.span),
+        }))
+    }
+
+    fn parse_var_decl_stmt(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let start = self.current_token().span.clone();
+        let is_const = self.current_token().token_kind == CONST;
+        let doc = self.current_token().doc_comment.clone();
         self.advance();
-        id = self.expect(IDENTIFIER)?.token_value;
+        let pattern = self.parse_binding_pattern()?;
         let mut value_type = None;
 
         if self.current_token().token_kind == COLON {
             self.advance();
 
-            value_type = Some(self.expect(IDENTIFIER)?.token_value);
+            value_type = Some(self.parse_type()?.token_value);
         }
-        let value: Option<Box<dyn Compilable>>;
-        if self.current_token().token_kind == EQUAL {
+        let value = if self.current_token().token_kind == EQUAL {
             self.advance();
-            value = Some(self.parse_expr()?);
+            Some(self.parse_expr()?)
         } else {
-            value = None
-        }
+            None
+        };
         Ok(Box::new(VariableDefineNode {
             value_type,
             value,
-            var_name: id,
+            pattern,
             is_const,
+            span: start.to(&self.previous_token().span),
+            doc,
+        }))
+    }
+
+    /// Parses the name a `var` binds, or `(a, b)` to take a tuple apart.
+    fn parse_binding_pattern(&mut self) -> Result<BindingPattern, ParserError> {
+        if self.current_token().token_kind != LEFTPAREN {
+            return Ok(BindingPattern::Name(self.expect(IDENTIFIER)?.token_value));
+        }
+        self.advance();
+        let mut elements = vec![self.parse_binding_pattern()?];
+        while self.current_token().token_kind == COMMA {
+            self.advance();
+            elements.push(self.parse_binding_pattern()?);
+        }
+        self.expect(RIGHTPAREN)?;
+        Ok(BindingPattern::Tuple(elements))
+    }
+
+    fn parse_while(
+        &mut self,
+        start: &Span,
+        label: Option<String>,
+    ) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(WHILE)?;
+        self.expect(LEFTPAREN)?;
+        let condition = self.parse_expr()?;
+        self.expect(RIGHTPAREN)?;
+        let body = self.parse_block()?;
+        Ok(Box::new(WhileStatement {
+            condition,
+            body,
+            label,
+            span: start.to(&self.previous_token().span),
         }))
     }
 
+    fn parse_loop(
+        &mut self,
+        start: &Span,
+        label: Option<String>,
+    ) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(LOOP)?;
+        let body = self.parse_block()?;
+        Ok(Box::new(LoopStatement {
+            body,
+            label,
+            span: start.to(&self.previous_token().span),
+        }))
+    }
+
+    fn parse_for(
+        &mut self,
+        start: &Span,
+        label: Option<String>,
+    ) -> Result<Box<dyn Compilable>, ParserError> {
+        self.expect(FOR)?;
+        let variable = self.expect(IDENTIFIER)?.token_value;
+        self.expect(IN)?;
+        let range_start = self.parse_expr()?;
+        let inclusive = self.current_token().token_kind == DOTDOTEQUAL;
+        if !inclusive {
+            self.expect(DOTDOT)?;
+        } else {
+            self.advance();
+        }
+        let end = self.parse_expr()?;
+        let mut step = None;
+        if self.current_token().token_kind == STEP {
+            self.advance();
+            step = Some(self.parse_expr()?);
+        }
+        let body = self.parse_block()?;
+        Ok(Box::new(ForStatement {
+            variable,
+            start: range_start,
+            end,
+            step,
+            inclusive,
+            body,
+            label,
+            span: start.to(&self.previous_token().span),
+        }))
+    }
+
+    fn parse_match_arm(&mut self) -> Result<MatchArm, ParserError> {
+        let start = self.current_token().span.clone();
+        let pattern = match self.current_token().token_kind {
+            IDENTIFIER if self.current_token().token_value == "_" => {
+                self.advance();
+                MatchPattern::Wildcard(start.clone())
+            }
+            TRUE | FALSE => {
+                let value = self.current_token().token_kind == TRUE;
+                self.advance();
+                MatchPattern::Bool(value, start.clone())
+            }
+            NUMB | FLOAT | STRING | MINUS => {
+                let value = self.parse_unary()?;
+                match self.current_token().token_kind {
+                    DOTDOT | DOTDOTEQUAL => {
+                        let inclusive = self.current_token().token_kind == DOTDOTEQUAL;
+                        self.advance();
+                        MatchPattern::Range {
+                            start: value,
+                            end: self.parse_unary()?,
+                            inclusive,
+                        }
+                    }
+                    _ => MatchPattern::Value(value),
+                }
+            }
+            // `Shape.Circle(r)`, binding the payload to new variables
+            IDENTIFIER if self.peek() == DOT => {
+                let enum_name = self.current_token().token_value.clone();
+                self.advance();
+                self.advance();
+                let variant = self.expect(IDENTIFIER)?.token_value;
+                let mut bindings = Vec::new();
+                if self.current_token().token_kind == LEFTPAREN {
+                    self.advance();
+                    while self.current_token().token_kind != RIGHTPAREN {
+                        bindings.push(self.expect(IDENTIFIER)?.token_value);
+                        if self.current_token().token_kind != COMMA {
+                            break;
+                        }
+                        self.advance();
+                    }
+                    self.expect(RIGHTPAREN)?;
+                }
+                MatchPattern::Variant {
+                    enum_name,
+                    variant,
+                    bindings,
+                    span: start.to(&self.previous_token().span),
+                }
+            }
+            _ => {
+                return Err(UnexpectedToken {
+                    found: self.current_token().token_value.clone(),
+                    expected: VALUE,
+                    span: start,
+                });
+            }
+        };
+        self.expect(FATARROW)?;
+        let body = if self.current_token().token_kind == OPENINGBRACE {
+            self.parse_block()?
+        } else {
+            vec![self.parse_stmt()?]
+        };
+        Ok(MatchArm {
+            pattern,
+            body,
+            span: start.to(&self.previous_token().span),
+        })
+    }
+
+    /// Parses `(condition) { body }` of an `if` or `else if`.
+    fn parse_if_arm(&mut self, start: &Span) -> Result<IfBranch, ParserError> {
+        self.expect(LEFTPAREN)?;
+        let condition = self.parse_expr()?;
+        self.expect(RIGHTPAREN)?;
+        let body = self.parse_block()?;
+        Ok(IfBranch {
+            condition,
+            body,
+            span: start.to(&self.previous_token().span),
+        })
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<Box<dyn Compilable>>, ParserError> {
+        self.expect(OPENINGBRACE)?;
+        let mut body: Vec<Box<dyn Compilable>> = Vec::new();
+        while self.current_token().token_kind != CLOSINGBRACE {
+            if self.current_token().token_kind == EOF {
+                return Err(ParserError::UnexpectedToken {
+                    found: "EOF".into(),
+                    expected: CLOSINGBRACE,
+                    span: self.current_token().span.clone(),
+                });
+            }
+            body.push(self.parse_stmt()?);
+        }
+        self.expect(CLOSINGBRACE)?;
+        Ok(body)
+    }
+
     fn parse_expr(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
-        //let mut comp:Box<dyn Compilable>;
-        //if self.current_token()==MINUS {
-          //  comp
-        //}
+        self.parse_or()
+    }
 
-        let comp = self.parse_comparison();
-        comp
+    fn parse_or(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let mut factor = self.parse_and()?;
+        while self.current_token().token_kind == OR {
+            self.advance();
+            let right = self.parse_and()?;
+            factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
+                left: factor,
+                right,
+                op_tok: OR,
+            });
+        }
+        Ok(factor)
+    }
+
+    fn parse_and(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let mut factor = self.parse_equality()?;
+        while self.current_token().token_kind == AND {
+            self.advance();
+            let right = self.parse_equality()?;
+            factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
+                left: factor,
+                right,
+                op_tok: AND,
+            });
+        }
+        Ok(factor)
+    }
+
+    fn parse_equality(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let mut factor = self.parse_comparison()?;
+        while self.current_token().token_kind == EQUALEQUAL
+            || self.current_token().token_kind == NOTEQUAL
+        {
+            let operator = self.current_token().token_kind.clone();
+            self.advance();
+            let right = self.parse_comparison()?;
+            factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
+                left: factor,
+                right,
+                op_tok: operator,
+            });
+        }
+        Ok(factor)
     }
 
     fn parse_comparison(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
         let mut factor = self.parse_term()?;
-        while self.current_token().token_kind == GREATER || self.current_token().token_kind == LESS
+        while self.current_token().token_kind == GREATER
+            || self.current_token().token_kind == LESS
+            || self.current_token().token_kind == GREATEREQUAL
+            || self.current_token().token_kind == LESSEQUAL
         {
             let operator = self.current_token().token_kind.clone();
             self.advance();
+            let right = self.parse_term()?;
             factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
                 left: factor,
-                right: self.parse_term()?,
+                right,
                 op_tok: operator,
             });
         }
+        // `value is Enum.Variant`
+        if self.current_token().token_kind == IS {
+            self.advance();
+            let enum_name = self.expect(IDENTIFIER)?.token_value;
+            self.expect(DOT)?;
+            let variant = self.expect(IDENTIFIER)?;
+            factor = Box::new(IsVariantNode {
+                span: factor.span().to(&variant.span),
+                value: factor,
+                enum_name,
+                variant: variant.token_value,
+            });
+        }
         Ok(factor)
     }
     fn parse_term(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
@@ -227,9 +717,11 @@ impl Parser {
         while self.current_token().token_kind == MINUS || self.current_token().token_kind == PLUS {
             let operator = self.current_token().token_kind.clone();
             self.advance();
+            let right = self.parse_factor()?;
             factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
                 left: factor,
-                right: self.parse_factor()?,
+                right,
                 op_tok: operator,
             });
         }
@@ -244,9 +736,11 @@ impl Parser {
         {
             let operator = self.current_token().token_kind.clone();
             self.advance();
+            let right = self.parse_unary()?;
             factor = Box::new(BinaryOpNode {
+                span: factor.span().to(right.span()),
                 left: factor,
-                right: self.parse_unary()?,
+                right,
                 op_tok: operator,
             });
         }
@@ -254,79 +748,395 @@ impl Parser {
     }
 
     fn parse_unary(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let span = self.current_token().span.clone();
+        if self.current_token().token_kind == NOT
+            || self.current_token().token_kind == MINUS
+            || self.current_token().token_kind == PLUS
+        {
+            let prefix = self.current_token().token_kind.clone();
+            self.advance();
+            let value = self.parse_unary()?;
+            Ok(Box::new(PrefixExpressionNode {
+                span: span.to(value.span()),
+                prefix,
+                value,
+            }))
+        } else {
+            self.parse_postfix()
+        }
+    }
+
+    /// Parses a value followed by any number of `.field` accesses, `.method()` calls and `[index]`es.
+    fn parse_postfix(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        // A bare name in front of `.name` may be a type, for static calls like `Point.new()`
+        // and enum variants like `Shape.Empty`
+        let mut receiver_name = (self.current_token().token_kind == IDENTIFIER
+            && self.peek() == DOT)
+            .then(|| self.current_token().token_value.clone());
+        let mut value = self.parse_primary()?;
+        loop {
+            if self.current_token().token_kind == LEFTBRACKET {
+                self.advance();
+                let index = self.parse_expr()?;
+                let closing = self.expect(RIGHTBRACKET)?;
+                value = Box::new(IndexNode {
+                    span: value.span().to(&closing.span),
+                    object: value,
+                    index,
+                });
+                receiver_name = None;
+                continue;
+            }
+            // `make_adder(2)(3)` calls the returned function
+            if self.current_token().token_kind == LEFTPAREN {
+                let (args, named_args) = self.parse_call_args()?;
+                value = Box::new(CallValueNode {
+                    span: value.span().to(&self.previous_token().span),
+                    callee: value,
+                    args,
+                    named_args,
+                });
+                receiver_name = None;
+                continue;
+            }
+            if self.current_token().token_kind != DOT {
+                break;
+            }
+            self.advance();
+            // `t.0` reads an element of a tuple
+            let field = if self.current_token().token_kind == NUMB {
+                self.expect(NUMB)?
+            } else {
+                self.expect(IDENTIFIER)?
+            };
+            if field.token_kind == IDENTIFIER && self.current_token().token_kind == LEFTPAREN {
+                let (args, named_args) = self.parse_call_args()?;
+                value = Box::new(MethodCallNode {
+                    span: value.span().to(&self.previous_token().span),
+                    object: value,
+                    receiver_name: receiver_name.take(),
+                    call: FunctionCallNode {
+                        args,
+                        named_args,
+                        name: field.token_value,
+                        call_type: Fn,
+                        span: field.span.to(&self.previous_token().span),
+                    },
+                });
+            } else {
+                value = Box::new(FieldAccessNode {
+                    span: value.span().to(&field.span),
+                    object: value,
+                    receiver_name: receiver_name.take(),
+                    field: field.token_value,
+                });
+            }
+            receiver_name = None;
+        }
+        Ok(value)
+    }
+
+    /// Parses `(args)` of a call, positional arguments first and named ones after them.
+    fn parse_call_args(&mut self) -> Result<CallArguments, ParserError> {
+        self.expect(LEFTPAREN)?;
+        let mut args: Vec<Box<dyn Compilable>> = Vec::new();
+        let mut named_args = Vec::new();
+
+        if self.current_token().token_kind != RIGHTPAREN {
+            loop {
+                if self.current_token().token_kind == IDENTIFIER && self.peek() == EQUAL {
+                    let name = self.current_token().clone();
+                    self.advance();
+                    self.advance();
+                    let value = self.parse_expr()?;
+                    named_args.push(NamedArgument {
+                        span: name.span.to(value.span()),
+                        name: name.token_value,
+                        value,
+                    });
+                } else if !named_args.is_empty() {
+                    // Positional arguments can't follow named ones
+                    return Err(UnexpectedToken {
+                        found: self.current_token().token_value.clone(),
+                        expected: IDENTIFIER,
+                        span: self.current_token().span.clone(),
+                    });
+                } else {
+                    args.push(self.parse_expr()?);
+                }
+
+                if self.current_token().token_kind == COMMA {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        self.expect(RIGHTPAREN)?;
+        Ok((args, named_args))
+    }
+
+    fn parse_primary(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let span = self.current_token().span.clone();
         if self.current_token().token_kind == FLOAT {
             let value = match self.current_token().token_value.parse::<f32>() {
                 Err(_) => unreachable!(),
                 Ok(numb) => numb,
             };
             self.advance();
-            Ok(Box::new(FloatNode { number: value }))
+            Ok(Box::new(FloatNode { number: value, span }))
         } else if self.current_token().token_kind == TRUE
             || self.current_token().token_kind == FALSE
         {
             let value = self.current_token().token_kind.clone();
             self.advance();
-            Ok(Box::new(BoolNode { value }))
+            Ok(Box::new(BoolNode { value, span }))
+        } else if self.current_token().token_kind == PIPE
+            || (self.current_token().token_kind == OR && self.current_token().token_value == "||")
+        {
+            self.parse_lambda()
+        } else if self.current_token().token_kind == UNDEF {
+            self.advance();
+            Ok(Box::new(UndefNode { span }))
         } else if self.current_token().token_kind == NUMB {
             let value = match self.current_token().token_value.parse::<i64>() {
                 Ok(numb) => numb,
                 Err(_) => unreachable!(),
             };
             self.advance();
-            Ok(Box::new(NumberNode { number: value }))
+            Ok(Box::new(NumberNode { number: value, span }))
         } else if self.current_token().token_kind == IDENTIFIER {
             let value = self.current_token().token_value.clone();
             self.advance();
 
             if self.current_token().token_kind == LEFTPAREN {
-                self.advance();
-                let mut args: Vec<Box<dyn Compilable>> = Vec::new();
-
-                if self.current_token().token_kind != RIGHTPAREN {
-                    loop {
-                        args.push(self.parse_expr()?);
-
-                        if self.current_token().token_kind == COMMA {
-                            self.advance();
-                        } else {
-                            break;
-                        }
-                    }
-                }
-
-                self.expect(RIGHTPAREN)?;
+                let (args, named_args) = self.parse_call_args()?;
                 let is_macro = value.ends_with('!');
                 let name = value.trim_end_matches('!').to_string();
 
                 Ok(Box::new(FunctionCallNode {
                     args,
+                    named_args,
                     name,
                     call_type: if is_macro { Macro } else { Fn },
+                    span: span.to(&self.previous_token().span),
                 }))
+            } else if self.current_token().token_kind == OPENINGBRACE
+                && self.peek() == IDENTIFIER
+                && self.peek_nth(2) == COLON
+            {
+                self.parse_struct_literal(value, span)
             } else {
                 Ok(Box::new(VariableAccessNode {
                     variable_name: value,
+                    span,
                 }))
             }
+        } else if self.current_token().token_kind == LEFTBRACKET {
+            self.advance();
+            let mut elements = Vec::new();
+            while self.current_token().token_kind != RIGHTBRACKET {
+                elements.push(self.parse_expr()?);
+                if self.current_token().token_kind != COMMA {
+                    break;
+                }
+                self.advance();
+            }
+            let closing = self.expect(RIGHTBRACKET)?;
+            Ok(Box::new(ArrayNode {
+                elements,
+                span: span.to(&closing.span),
+            }))
+        } else if self.current_token().token_kind == OPENINGBRACE {
+            self.advance();
+            let mut entries = Vec::new();
+            while self.current_token().token_kind != CLOSINGBRACE {
+                let key = self.parse_expr()?;
+                self.expect(COLON)?;
+                entries.push((key, self.parse_expr()?));
+                if self.current_token().token_kind != COMMA {
+                    break;
+                }
+                self.advance();
+            }
+            let closing = self.expect(CLOSINGBRACE)?;
+            Ok(Box::new(MapNode {
+                entries,
+                span: span.to(&closing.span),
+            }))
         } else if self.current_token().token_kind == LEFTPAREN {
             self.advance();
             let value = self.parse_expr()?;
-            self.expect(RIGHTPAREN)?;
-            Ok(value)
+            if self.current_token().token_kind != COMMA {
+                self.expect(RIGHTPAREN)?;
+                return Ok(value);
+            }
+            // `(a, b)` is a tuple
+            let mut elements = vec![value];
+            while self.current_token().token_kind == COMMA {
+                self.advance();
+                if self.current_token().token_kind == RIGHTPAREN {
+                    break;
+                }
+                elements.push(self.parse_expr()?);
+            }
+            let closing = self.expect(RIGHTPAREN)?;
+            Ok(Box::new(TupleNode {
+                elements,
+                span: span.to(&closing.span),
+            }))
         } else if self.current_token().token_kind == STRING {
             let value = StringNode {
                 value: self.current_token().token_value.clone(),
+                span,
             };
             self.advance();
             Ok(Box::new(value))
+        } else if self.current_token().token_kind == INTERPOLATIONSTART {
+            self.advance();
+            let mut parts = vec![InterpolationPart::Text(self.expect(STRING)?.token_value)];
+            while self.current_token().token_kind != INTERPOLATIONEND {
+                parts.push(InterpolationPart::Expr(self.parse_expr()?));
+                parts.push(InterpolationPart::Text(self.expect(STRING)?.token_value));
+            }
+            self.advance();
+            Ok(Box::new(InterpolatedStringNode { parts, span }))
         } else {
             Err(UnexpectedToken {
                 found: self.current_token().token_value.clone(),
                 expected: VALUE,
+                span,
             })
         }
     }
 
+    /// Parses a type like `numb`, `numb[]`, `map<string, numb>` or `(numb, string)`, the token value is the whole type.
+    fn parse_type(&mut self) -> Result<Token, ParserError> {
+        if self.current_token().token_kind == FNC {
+            return self.parse_function_type();
+        }
+        let mut type_token = if self.current_token().token_kind == LEFTPAREN {
+            let mut tuple_token = self.current_token().clone();
+            self.advance();
+            let mut elements = vec![self.parse_type()?.token_value];
+            while self.current_token().token_kind == COMMA {
+                self.advance();
+                elements.push(self.parse_type()?.token_value);
+            }
+            let closing = self.expect(RIGHTPAREN)?;
+            // `(numb)` is just `numb`, `(fnc(): numb)[]` keeps them so `[]` isn't part of the return type
+            tuple_token.token_value = match elements.len() {
+                1 if !elements[0].starts_with("fnc(") => elements.remove(0),
+                _ => format!("({})", elements.join(", ")),
+            };
+            tuple_token.span = tuple_token.span.to(&closing.span);
+            tuple_token
+        } else {
+            self.expect(IDENTIFIER)?
+        };
+        if type_token.token_value == "map" && self.current_token().token_kind == LESS {
+            self.advance();
+            let key = self.parse_type()?;
+            self.expect(COMMA)?;
+            let value = self.parse_type()?;
+            let closing = self.expect(GREATER)?;
+            type_token.token_value = format!("map<{}, {}>", key.token_value, value.token_value);
+            type_token.span = type_token.span.to(&closing.span);
+        }
+        // `numb?[]` is an array of optionals, `numb[]?` an optional array
+        loop {
+            let closing = if self.current_token().token_kind == LEFTBRACKET && self.peek() == RIGHTBRACKET {
+                self.advance();
+                type_token.token_value.push_str("[]");
+                self.expect(RIGHTBRACKET)?
+            } else if self.current_token().token_kind == QUESTION {
+                type_token.token_value.push('?');
+                self.expect(QUESTION)?
+            } else {
+                break;
+            };
+            type_token.span = type_token.span.to(&closing.span);
+        }
+        Ok(type_token)
+    }
+
+    /// Parses `fnc(numb, string): numb`, a missing return type means `void`.
+    fn parse_function_type(&mut self) -> Result<Token, ParserError> {
+        let mut type_token = self.expect(FNC)?;
+        self.expect(LEFTPAREN)?;
+        let mut params = Vec::new();
+        while self.current_token().token_kind != RIGHTPAREN {
+            params.push(self.parse_type()?.token_value);
+            if self.current_token().token_kind != COMMA {
+                break;
+            }
+            self.advance();
+        }
+        self.expect(RIGHTPAREN)?;
+        type_token.token_value = format!("fnc({})", params.join(", "));
+        if self.current_token().token_kind == COLON {
+            self.advance();
+            let return_type = self.parse_type()?.token_value;
+            type_token.token_value = format!("{}: {}", type_token.token_value, return_type);
+        }
+        type_token.span = type_token.span.to(&self.previous_token().span);
+        Ok(type_token)
+    }
+
+    /// Parses `|x: numb| x * 2` or `|x: numb|: numb { ... }`, `||` starts a lambda without parameters.
+    fn parse_lambda(&mut self) -> Result<Box<dyn Compilable>, ParserError> {
+        let start = self.current_token().span.clone();
+        let mut args = Vec::new();
+        if self.current_token().token_kind == OR {
+            self.advance();
+        } else {
+            self.expect(PIPE)?;
+            while self.current_token().token_kind != PIPE {
+                let arg_name = self.expect(IDENTIFIER)?;
+                self.expect(COLON)?;
+                let arg_type = self.parse_type()?;
+                args.push(FunctionArgs {
+                    span: arg_name.span.to(&arg_type.span),
+                    name: arg_name.token_value,
+                    argument_type: arg_type.token_value,
+                    default: None,
+                });
+                if self.current_token().token_kind != COMMA {
+                    break;
+                }
+                self.advance();
+            }
+            self.expect(PIPE)?;
+        }
+        // A return type is only written for block bodies
+        let return_type = if self.current_token().token_kind == COLON {
+            self.advance();
+            let return_type = self.parse_type()?.token_value;
+            if self.current_token().token_kind != OPENINGBRACE {
+                return Err(UnexpectedToken {
+                    found: self.current_token().token_value.clone(),
+                    expected: OPENINGBRACE,
+                    span: self.current_token().span.clone(),
+                });
+            }
+            Some(return_type)
+        } else {
+            None
+        };
+        let body = if self.current_token().token_kind == OPENINGBRACE {
+            LambdaBody::Block(self.parse_block()?)
+        } else {
+            LambdaBody::Expr(self.parse_expr()?)
+        };
+        Ok(Box::new(LambdaNode {
+            args,
+            return_type,
+            body,
+            span: start.to(&self.previous_token().span),
+        }))
+    }
+
     fn expect(&mut self, token_kind: TokenKind) -> Result<Token, ParserError> {
         if self.current_token().token_kind == token_kind {
             let token = self.current_token().clone();
@@ -336,12 +1146,22 @@ impl Parser {
             Err(UnexpectedToken {
                 expected: token_kind,
                 found: self.current_token().token_value.clone(),
+                span: self.current_token().span.clone(),
             })
         }
     }
 
     fn peek(&self) -> TokenKind {
-        let idx = self.token_idx + 1;
+        self.peek_nth(1)
+    }
+
+    fn peek_nth(&self, n: usize) -> TokenKind {
+        let idx = (self.token_idx + n).min(self.tokens.len() - 1);
         self.tokens[idx].token_kind.clone()
     }
+
+    /// Token consumed last; used to close the span of a finished node.
+    fn previous_token(&self) -> &Token {
+        &self.tokens[self.token_idx.saturating_sub(1)]
+    }
 }
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use crate::backend::{
+    ast::nodes::FunctionCallNode,
+    compiler::{
+        byte_code::{indent_fn, Compilable, Compiler},
+        comptime_variable_checker::comptime_value_for_check::ComptimeValueType,
+        enums_compiler_context::CompileTimeEnumForCheck,
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+#[derive(Clone, Debug)]
+pub struct EnumVariant {
+    pub name: String,
+    /// Types of the values the variant holds, empty for variants like `Empty`
+    pub payload: Vec<String>,
+    pub span: Span,
+}
+
+/*
+ * Enum define node
+ */
+#[derive(Clone)]
+pub struct EnumDefineNode {
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+    pub span: Span,
+}
+
+impl Compilable for EnumDefineNode {
+    fn declare_types(&self, compiler: &mut Compiler) -> Result<(), CompileError> {
+        let mut seen = HashSet::new();
+        for variant in &self.variants {
+            if !seen.insert(&variant.name) {
+                return Err(CompileError::DuplicateVariant {
+                    variant: variant.name.clone(),
+                    span: variant.span.clone(),
+                });
+            }
+        }
+        compiler.context.add_enum(
+            self.name.clone(),
+            CompileTimeEnumForCheck {
+                variants: self.variants.clone(),
+            },
+            &self.span,
+        )
+    }
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        // Enums outside of the top level are not hoisted
+        if !compiler.context.enums.contains_key(&self.name) {
+            self.declare_types(compiler)?;
+        }
+        for variant in &self.variants {
+            for payload_type in &variant.payload {
+                compiler.context.get_type(payload_type, &variant.span)?;
+            }
+        }
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Enum {}", indent_fn(indent), self.name)?;
+        for variant in &self.variants {
+            writeln!(
+                f,
+                "{}{}({})",
+                indent_fn(indent + 1),
+                variant.name,
+                variant.payload.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+impl Debug for EnumDefineNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+/// Enum named by the bare identifier in front of `.Variant`, variables shadow enums.
+pub fn enum_receiver(compiler: &Compiler, receiver_name: Option<&String>) -> Option<String> {
+    receiver_name
+        .filter(|name| compiler.context.get_variable(name).is_none())
+        .filter(|name| compiler.context.enums.contains_key(*name))
+        .cloned()
+}
+
+/// Tag and payload types of `variant` in the enum `enum_name`.
+pub fn find_variant(
+    compiler: &Compiler,
+    enum_name: &str,
+    variant: &str,
+    span: &Span,
+) -> Result<(usize, Vec<ComptimeValueType>), CompileError> {
+    let definition = compiler.context.get_enum(enum_name, span)?;
+    let (tag, found) = definition
+        .variant(variant)
+        .ok_or_else(|| CompileError::UnknownVariant {
+            enum_name: enum_name.to_string(),
+            variant: variant.to_string(),
+            span: span.clone(),
+        })?;
+    let payload = found
+        .payload
+        .iter()
+        .map(|payload_type| compiler.context.get_type(payload_type, &found.span))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((tag, payload))
+}
+
+/// Builds `Shape.Circle(2.0)`, or `Shape.Empty` when there is no `call`.
+pub fn compile_variant(
+    compiler: &mut Compiler,
+    enum_name: &str,
+    variant: &str,
+    call: Option<&FunctionCallNode>,
+    span: &Span,
+) -> Result<ComptimeValueType, CompileError> {
+    let (tag, payload) = find_variant(compiler, enum_name, variant, span)?;
+    let full_name = format!("{}.{}", enum_name, variant);
+    let args = call.map(|call| &call.args[..]).unwrap_or_default();
+    if let Some(named) = call.and_then(|call| call.named_args.first()) {
+        return Err(CompileError::UnknownArgument {
+            function: full_name,
+            argument: named.name.clone(),
+            span: named.span.clone(),
+        });
+    }
+    if args.len() != payload.len() {
+        return Err(CompileError::VariantPayloadCount {
+            variant: full_name,
+            expected: payload.len(),
+            found: args.len(),
+            span: span.clone(),
+        });
+    }
+    for (arg, expected) in args.iter().zip(payload) {
+        let found = arg.compile(compiler)?;
+        if !expected.accepts(&found) {
+            return Err(CompileError::TypeMismatch {
+                expected,
+                found,
+                span: arg.span().clone(),
+            });
+        }
+    }
+    compiler
+        .out
+        .push(Instructions::MakeEnum(enum_name.to_string(), tag, args.len()));
+    Ok(ComptimeValueType::Enum(enum_name.to_string()))
+}
+
+/*
+ * Is node
+ */
+#[derive(Clone)]
+pub struct IsVariantNode {
+    pub value: Box<dyn Compilable>,
+    pub enum_name: String,
+    pub variant: String,
+    pub span: Span,
+}
+
+impl Compilable for IsVariantNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let expected = ComptimeValueType::Enum(self.enum_name.clone());
+        let found = self.value.compile(compiler)?;
+        if found != expected {
+            return Err(CompileError::TypeMismatch {
+                expected,
+                found,
+                span: self.value.span().clone(),
+            });
+        }
+        let (tag, _) = find_variant(compiler, &self.enum_name, &self.variant, &self.span)?;
+        compiler.out.push(Instructions::IsVariant(tag));
+        Ok(ComptimeValueType::Bool)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Is({}.{})", indent_fn(indent), self.enum_name, self.variant)?;
+        self.value.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.value.as_ref()]
+    }
+}
+impl Debug for IsVariantNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
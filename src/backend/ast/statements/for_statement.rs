@@ -0,0 +1,166 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    compiler::{
+        byte_code::{Compilable, Compiler, indent_fn, nodes},
+        comptime_variable_checker::{
+            comptime_context::ComptimeVariable,
+            comptime_value_for_check::ComptimeValueType,
+        },
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+/// `for i in start..end step n { }`, `end` is included when `inclusive` is set (`..=`).
+#[derive(Clone)]
+pub struct ForStatement {
+    pub variable: String,
+    pub start: Box<dyn Compilable>,
+    pub end: Box<dyn Compilable>,
+    pub step: Option<Box<dyn Compilable>>,
+    pub inclusive: bool,
+    pub body: Vec<Box<dyn Compilable>>,
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+fn expect_int(compiler: &mut Compiler, expr: &dyn Compilable) -> Result<(), CompileError> {
+    let found = expr.compile(compiler)?;
+    if found != ComptimeValueType::Int {
+        return Err(CompileError::TypeMismatch {
+            expected: ComptimeValueType::Int,
+            found,
+            span: expr.span().clone(),
+        });
+    }
+    Ok(())
+}
+
+impl ForStatement {
+    /// Evaluates the step into `step_var`, it has to be greater than 0 or the loop never ends.
+    fn compile_step(&self, compiler: &mut Compiler, step_var: &str) -> Result<(), CompileError> {
+        let Some(step) = &self.step else {
+            compiler.out.push(Instructions::PushNumber(1.0));
+            compiler.out.push(Instructions::SaveVar(step_var.to_string()));
+            return Ok(());
+        };
+        let step_start = compiler.out.len();
+        expect_int(compiler, step.as_ref())?;
+        let constant = match &compiler.out[step_start..] {
+            [Instructions::PushNumber(n)] => Some(*n),
+            [Instructions::PushNumber(n), Instructions::Negate] => Some(-*n),
+            _ => None,
+        };
+        if constant.is_some_and(|n| n <= 0.0) {
+            return Err(CompileError::InvalidStep {
+                span: step.span().clone(),
+            });
+        }
+        compiler.out.push(Instructions::SaveVar(step_var.to_string()));
+        if constant.is_none() {
+            compiler.out.push(Instructions::LoadVar(step_var.to_string()));
+            compiler.out.push(Instructions::PushNumber(0.0));
+            compiler.out.push(Instructions::GreaterThan);
+            let jump_pos = compiler.out.len();
+            compiler.out.push(Instructions::JumpIfTrue(0));
+            compiler.out.push(Instructions::PushString(
+                "The step of a for loop must be greater than 0".to_string(),
+            ));
+            compiler.out.push(Instructions::Throw);
+            compiler.out[jump_pos] = Instructions::JumpIfTrue(compiler.out.len());
+        }
+        Ok(())
+    }
+}
+
+impl Compilable for ForStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        compiler.context.enter_scope();
+
+        // The counter has a name of its own, so a variable of the same name
+        // outside of the loop keeps its value
+        let counter = compiler.temp_variable(&self.variable);
+        // Bounds and step are evaluated once, before the first iteration
+        expect_int(compiler, self.start.as_ref())?;
+        compiler.out.push(Instructions::SaveVar(counter.clone()));
+        let end_var = compiler.temp_variable("for_end");
+        expect_int(compiler, self.end.as_ref())?;
+        compiler.out.push(Instructions::SaveVar(end_var.clone()));
+        let step_var = compiler.temp_variable("for_step");
+        self.compile_step(compiler, &step_var)?;
+
+        compiler.context.add_variable(
+            self.variable.clone(),
+            ComptimeVariable {
+                value_type: ComptimeValueType::Int,
+                is_const: true,
+                storage: Some(counter.clone()),
+            },
+            &self.span,
+        )?;
+
+        let condition_start = compiler.out.len();
+        compiler.out.push(Instructions::LoadVar(counter.clone()));
+        compiler.out.push(Instructions::LoadVar(end_var));
+        compiler.out.push(if self.inclusive {
+            Instructions::LessEqual
+        } else {
+            Instructions::LessThan
+        });
+        let jump_if_false_pos = compiler.out.len();
+        compiler.out.push(Instructions::JumpIfFalse(0));
+
+        compiler.enter_loop(self.label.clone());
+        compiler.context.enter_scope();
+        for stmt in &self.body {
+            stmt.compile(compiler)?;
+        }
+        compiler.context.exit_scope();
+
+        let increment_start = compiler.out.len();
+        compiler.out.push(Instructions::LoadVar(counter.clone()));
+        compiler.out.push(Instructions::LoadVar(step_var));
+        compiler.out.push(Instructions::Add);
+        compiler.out.push(Instructions::SaveVar(counter));
+        compiler.out.push(Instructions::Jump(condition_start));
+
+        let end = compiler.out.len();
+        compiler.out[jump_if_false_pos] = Instructions::JumpIfFalse(end);
+        compiler.exit_loop(increment_start, end);
+        compiler.context.exit_scope();
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}for {} in", indent_fn(indent), self.variable)?;
+        self.start.fmt_with_indent(f, indent + 2)?;
+        self.end.fmt_with_indent(f, indent + 2)?;
+        if let Some(step) = &self.step {
+            step.fmt_with_indent(f, indent + 2)?;
+        }
+        for stmt in &self.body {
+            stmt.fmt_with_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.start.as_ref(), self.end.as_ref()];
+        children.extend(self.step.iter().map(|step| step.as_ref()));
+        children.extend(nodes(&self.body));
+        children
+    }
+    fn breaks_out(&self, label: Option<&str>, _innermost: bool) -> bool {
+        label.is_some() && label != self.label.as_deref()
+            && self.body.iter().any(|stmt| stmt.breaks_out(label, false))
+    }
+}
+
+impl Debug for ForStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
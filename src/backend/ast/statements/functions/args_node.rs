@@ -1,5 +1,19 @@
+use crate::backend::{compiler::byte_code::Compilable, lexer::tokens::Span};
+
 #[derive(Debug,Clone)]
 pub struct FunctionArgs {
     pub argument_type: String,
     pub name: String,
+    /// Value used when a call leaves the argument out
+    pub default: Option<Box<dyn Compilable>>,
+    pub span: Span,
+}
+
+/// Type parameter of a generic function, like `T: numeric` in `fnc max<T: numeric>(...)`
+#[derive(Debug, Clone)]
+pub struct TypeParam {
+    pub name: String,
+    /// `numeric` or `printable`, checked against the type inferred at each call
+    pub constraint: Option<String>,
+    pub span: Span,
 }
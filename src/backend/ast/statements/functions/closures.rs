@@ -0,0 +1,253 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    ast::{nodes::NamedArgument, statements::functions::args_node::FunctionArgs},
+    compiler::{
+        byte_code::{Compilable, Compiler, check_defined, indent_fn, nodes},
+        comptime_variable_checker::{
+            comptime_context::ComptimeVariable, comptime_value_for_check::ComptimeValueType,
+        },
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+#[derive(Clone, Debug)]
+pub enum LambdaBody {
+    /// `|x: numb| x * 2`, returns the value of the expression
+    Expr(Box<dyn Compilable>),
+    /// `|x: numb|: numb { return x * 2; }`
+    Block(Vec<Box<dyn Compilable>>),
+}
+
+/*
+ * Lambda node
+ */
+#[derive(Clone)]
+pub struct LambdaNode {
+    pub args: Vec<FunctionArgs>,
+    pub return_type: Option<String>,
+    pub body: LambdaBody,
+    pub span: Span,
+}
+
+impl LambdaNode {
+    /// Compiles the body and returns its return type, the parameters are already declared.
+    fn compile_body(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        match &self.body {
+            LambdaBody::Expr(expr) => {
+                let return_type = expr.compile(compiler)?;
+                compiler.out.push(if return_type == ComptimeValueType::Void {
+                    Instructions::Return
+                } else {
+                    Instructions::ReturnValue
+                });
+                Ok(return_type)
+            }
+            LambdaBody::Block(body) => {
+                let return_type = match &self.return_type {
+                    Some(return_type) => compiler.context.get_type(return_type, &self.span)?,
+                    None => ComptimeValueType::Void,
+                };
+                compiler.return_types.push(return_type.clone());
+                let result = body.iter().try_for_each(|stmt| stmt.compile(compiler).map(|_| ()));
+                compiler.return_types.pop();
+                result?;
+                if return_type != ComptimeValueType::Void && !body.iter().any(|stmt| stmt.always_returns()) {
+                    return Err(CompileError::MissingReturn {
+                        name: "closure".to_string(),
+                        span: self.span.clone(),
+                    });
+                }
+                compiler.out.push(Instructions::Return);
+                Ok(return_type)
+            }
+        }
+    }
+}
+
+impl Compilable for LambdaNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let param_types = self
+            .args
+            .iter()
+            .map(|arg| compiler.context.get_type(&arg.argument_type, &arg.span))
+            .collect::<Result<Vec<_>, _>>()?;
+        let enclosing = compiler.context.local_variables();
+
+        // Like a function, the body is compiled in place and jumped over
+        let skip_body_pos = compiler.out.len();
+        compiler.out.push(Instructions::Jump(0));
+        let address = compiler.out.len();
+
+        compiler.context.enter_function();
+        // Captured values are copies, assigning to them would not change the original
+        for (name, value_type, storage) in &enclosing {
+            compiler.context.add_variable(
+                name.clone(),
+                ComptimeVariable {
+                    value_type: value_type.clone(),
+                    is_const: true,
+                    storage: storage.clone(),
+                },
+                &self.span,
+            )?;
+        }
+        compiler.context.enter_scope();
+        for arg in self.args.iter().rev() {
+            compiler.out.push(Instructions::SaveVar(arg.name.clone()));
+        }
+        for (arg, value_type) in self.args.iter().zip(&param_types) {
+            compiler.context.add_variable(
+                arg.name.clone(),
+                ComptimeVariable {
+                    value_type: value_type.clone(),
+                    is_const: false,
+                    storage: None,
+                },
+                &arg.span,
+            )?;
+        }
+        let outer_loops = std::mem::take(&mut compiler.loops);
+        let return_type = self.compile_body(compiler);
+        compiler.loops = outer_loops;
+        let return_type = return_type?;
+        compiler.context.exit_scope();
+        compiler.context.exit_function();
+
+        let end = compiler.out.len();
+        compiler.out[skip_body_pos] = Instructions::Jump(end);
+
+        // Only the locals the body reads are captured
+        let body = &compiler.out[address..end];
+        let captures = enclosing
+            .into_iter()
+            .map(|(name, _, storage)| storage.unwrap_or(name))
+            .filter(|name| {
+                body.iter().any(|instruction| match instruction {
+                    Instructions::LoadVar(loaded) | Instructions::LoadVarIndex(loaded) => loaded == name,
+                    Instructions::MakeClosure(_, captured) => captured.contains(name),
+                    _ => false,
+                })
+            })
+            .collect();
+        compiler.out.push(Instructions::MakeClosure(address, captures));
+        Ok(ComptimeValueType::Function(param_types, Box::new(return_type)))
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name, arg.argument_type))
+            .collect();
+        writeln!(f, "{}Lambda |{}|", indent_fn(indent), args.join(", "))?;
+        match &self.body {
+            LambdaBody::Expr(expr) => expr.fmt_with_indent(f, indent + 1),
+            LambdaBody::Block(body) => {
+                for stmt in body {
+                    stmt.fmt_with_indent(f, indent + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn calls_function(&self) -> bool {
+        true
+    }
+}
+impl Debug for LambdaNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+/// Calls the function value on top of the stack, `name` is used in diagnostics.
+pub fn compile_value_call(
+    compiler: &mut Compiler,
+    callee_type: ComptimeValueType,
+    name: &str,
+    args: &[Box<dyn Compilable>],
+    named_args: &[NamedArgument],
+    span: &Span,
+) -> Result<ComptimeValueType, CompileError> {
+    check_defined(&callee_type, span)?;
+    let ComptimeValueType::Function(params, return_type) = callee_type else {
+        return Err(CompileError::NotCallable {
+            found: callee_type,
+            span: span.clone(),
+        });
+    };
+    // Function values only know the types of their parameters, not their names
+    if let Some(named) = named_args.first() {
+        return Err(CompileError::UnknownArgument {
+            function: name.to_string(),
+            argument: named.name.clone(),
+            span: named.span.clone(),
+        });
+    }
+    if args.len() != params.len() {
+        return Err(CompileError::UnexpectedFunctionArguments {
+            name: name.to_string(),
+            expected: params.len(),
+            found: args.len(),
+            span: span.clone(),
+        });
+    }
+    for (arg, expected) in args.iter().zip(params) {
+        let found = arg.compile(compiler)?;
+        if !expected.accepts(&found) {
+            return Err(CompileError::TypeMismatch {
+                expected,
+                found,
+                span: arg.span().clone(),
+            });
+        }
+    }
+    compiler.out.push(Instructions::CallIndirect(args.len()));
+    Ok(*return_type)
+}
+
+/*
+ * Call value node
+ */
+#[derive(Clone)]
+pub struct CallValueNode {
+    /// Expression giving the function, like `make_adder(2)` in `make_adder(2)(3)`
+    pub callee: Box<dyn Compilable>,
+    pub args: Vec<Box<dyn Compilable>>,
+    pub named_args: Vec<NamedArgument>,
+    pub span: Span,
+}
+
+impl Compilable for CallValueNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let callee_type = self.callee.compile(compiler)?;
+        let name = format!("{:?}", callee_type);
+        compile_value_call(compiler, callee_type, &name, &self.args, &self.named_args, &self.span)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Call(...)", indent_fn(indent))?;
+        self.callee.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.callee.as_ref()];
+        children.extend(nodes(&self.args));
+        children.extend(self.named_args.iter().map(|arg| arg.value.as_ref()));
+        children
+    }
+    fn calls_function(&self) -> bool {
+        true
+    }
+}
+impl Debug for CallValueNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
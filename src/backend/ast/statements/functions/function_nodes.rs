@@ -1,11 +1,12 @@
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use crate::backend::{
-    ast::statements::functions::args_node::FunctionArgs,
+    ast::statements::functions::args_node::{FunctionArgs, TypeParam},
     compiler::{
-        byte_code::{Compilable, Compiler}, comptime_variable_checker::{comptime_context::CompileContext, comptime_value_for_check::ComptimeValueType}, functions_compiler_context::CompileTimeFunctionForCheck
+        byte_code::{Compilable, Compiler, indent_fn}, comptime_variable_checker::{comptime_context::ComptimeVariable, comptime_value_for_check::ComptimeValueType}, functions_compiler_context::CompileTimeFunctionForCheck, instructions::Instructions
     },
     errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
 };
 #[derive(Clone)]
 pub struct FunctionDefineNode {
@@ -13,30 +14,226 @@ pub struct FunctionDefineNode {
     pub id: String,
     pub body: Vec<Box<dyn Compilable>>,
     pub return_type: Option<String>,
+    pub span: Span,
+    /// `///` documentation written above the function.
+    pub doc: Option<String>,
+    /// Type of the `impl` block the function is defined in
+    pub owner: Option<String>,
+    /// `<T, U: numeric>` of a generic function, empty otherwise
+    pub type_params: Vec<TypeParam>,
+}
+
+impl FunctionDefineNode {
+    fn return_type(&self, compiler: &Compiler) -> Result<ComptimeValueType, CompileError> {
+        match &self.return_type {
+            Some(return_type) => compiler.context.get_type(return_type, &self.span),
+            None => Ok(ComptimeValueType::Void),
+        }
+    }
+    /// Type parameters standing for themselves, for checking the signature of a generic function.
+    pub fn signature_type_params(type_params: &[TypeParam]) -> HashMap<String, ComptimeValueType> {
+        type_params
+            .iter()
+            .map(|param| (param.name.clone(), ComptimeValueType::TypeParam(param.name.clone())))
+            .collect()
+    }
+    /// Compiles the body of a generic function for the types its type parameters are bound to.
+    pub fn compile_instance(
+        &self,
+        compiler: &mut Compiler,
+        address_name: String,
+        bindings: HashMap<String, ComptimeValueType>,
+    ) -> Result<(), CompileError> {
+        compiler.context.type_params.push(bindings);
+        let result = self.return_type(compiler).and_then(|return_type| {
+            self.compile_body(compiler, address_name, return_type)
+        });
+        compiler.context.type_params.pop();
+        result
+    }
+    /// Compiles the body in place, under the address `address_name`.
+    fn compile_body(
+        &self,
+        compiler: &mut Compiler,
+        address_name: String,
+        return_type: ComptimeValueType,
+    ) -> Result<(), CompileError> {
+        // The body is compiled in place, normal execution jumps over it
+        let skip_body_pos = compiler.out.len();
+        compiler.out.push(Instructions::Jump(0));
+        // Defaults are compiled here, so they see the variables around the definition,
+        // calls that leave a parameter out call its default
+        for arg in &self.args {
+            if let Some(default) = &arg.default {
+                self.compile_default(compiler, &address_name, arg, default.as_ref())?;
+            }
+        }
+        compiler
+            .function_addresses
+            .insert(address_name, compiler.out.len());
+
+        compiler.context.enter_function();
+        // Arguments are on the stack in call order, so the last one is on top
+        for arg in self.args.iter().rev() {
+            compiler.out.push(Instructions::SaveVar(arg.name.clone()));
+        }
+        for arg in &self.args {
+            compiler.context.add_variable(
+                arg.name.clone(),
+                ComptimeVariable {
+                    value_type: compiler.context.get_type(&arg.argument_type, &arg.span)?,
+                    // `self` is a copy, changes to it would be lost when the method returns
+                    is_const: arg.name == "self",
+                    storage: None,
+                },
+                &arg.span,
+            )?;
+        }
+        // `break`/`continue` can't reach loops around the definition
+        let outer_loops = std::mem::take(&mut compiler.loops);
+        compiler.return_types.push(return_type.clone());
+        let body = self.body.iter().try_for_each(|stmt| stmt.compile(compiler).map(|_| ()));
+        compiler.return_types.pop();
+        compiler.loops = outer_loops;
+        body?;
+        compiler.context.exit_function();
+        if return_type != ComptimeValueType::Void
+            && !self.body.iter().any(|stmt| stmt.always_returns())
+        {
+            return Err(CompileError::MissingReturn {
+                name: self.id.clone(),
+                span: self.span.clone(),
+            });
+        }
+        compiler.out.push(Instructions::Return);
+
+        let end = compiler.out.len();
+        compiler.out[skip_body_pos] = Instructions::Jump(end);
+        Ok(())
+    }
+    /// Compiles the default of `arg` as a function without parameters returning its value.
+    fn compile_default(
+        &self,
+        compiler: &mut Compiler,
+        address_name: &str,
+        arg: &FunctionArgs,
+        default: &dyn Compilable,
+    ) -> Result<(), CompileError> {
+        let expected = compiler.context.get_type(&arg.argument_type, &arg.span)?;
+        compiler
+            .function_addresses
+            .insert(Self::default_address(address_name, &arg.name), compiler.out.len());
+        compiler.context.enter_function();
+        let found = default.compile(compiler);
+        compiler.context.exit_function();
+        let found = found?;
+        if !expected.accepts(&found) {
+            return Err(CompileError::TypeMismatch {
+                expected,
+                found,
+                span: default.span().clone(),
+            });
+        }
+        compiler.out.push(Instructions::ReturnValue);
+        Ok(())
+    }
+    /// Name the default of the parameter `param` of the function at `address_name` is called by.
+    pub fn default_address(address_name: &str, param: &str) -> String {
+        format!("{}#{}", address_name, param)
+    }
+    /// Name the compiled body is called by, methods are prefixed with their type.
+    pub fn address_name(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{}.{}", owner, self.id),
+            None => self.id.clone(),
+        }
+    }
+    fn is_declared(&self, compiler: &Compiler) -> bool {
+        match &self.owner {
+            Some(owner) => compiler.context.get_method(owner, &self.id, &self.span).is_ok(),
+            None => compiler.context.functions.last().unwrap().contains_key(&self.id),
+        }
+    }
 }
 
 impl Compilable for FunctionDefineNode {
+    fn declare(&self, compiler: &mut Compiler) -> Result<(), CompileError> {
+        for param in &self.type_params {
+            if let Some(constraint) = &param.constraint
+                && ComptimeValueType::Void.satisfies(constraint).is_none()
+            {
+                return Err(CompileError::UnknownConstraint {
+                    constraint: constraint.clone(),
+                    span: param.span.clone(),
+                });
+            }
+        }
+        // The types in the signature may name the type parameters
+        compiler
+            .context
+            .type_params
+            .push(Self::signature_type_params(&self.type_params));
+        let signature = self.return_type(compiler).and_then(|return_type| {
+            for arg in &self.args {
+                compiler.context.get_type(&arg.argument_type, &arg.span)?;
+            }
+            Ok(return_type)
+        });
+        compiler.context.type_params.pop();
+        let function = CompileTimeFunctionForCheck {
+            is_pub: true,
+            return_type: signature?,
+            args: self.args.clone(),
+            type_params: self.type_params.clone(),
+        };
+        match &self.owner {
+            Some(owner) => compiler
+                .context
+                .add_method(owner, self.id.clone(), function, &self.span),
+            None => compiler.context.add_function(self.id.clone(), function, &self.span),
+        }
+    }
+
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        println!("{:?}",&self.return_type);
-        let return_type = CompileContext::get_type(&self.return_type.clone().unwrap())?;
-        let args = self.args.clone(); 
-        compiler.context.add_function(
-            self.id.clone(),
-            CompileTimeFunctionForCheck{
-                is_pub:true,
-                return_type:return_type.clone(),
-                args,
-                body:self.body.clone()
-          }
-        )?;
-        Ok(return_type)
-        
+        let address_name = self.address_name();
+        if compiler.function_addresses.contains_key(&address_name)
+            || compiler.generic_functions.contains_key(&address_name)
+        {
+            return Err(CompileError::FunctionAlredyExists {
+                name: address_name,
+                span: self.span.clone(),
+            });
+        }
+        // Functions outside of the top level are not hoisted
+        if !self.is_declared(compiler) {
+            self.declare(compiler)?;
+        }
+
+        // Generic bodies are compiled once for every set of types they are called with
+        if !self.type_params.is_empty() {
+            compiler
+                .generic_functions
+                .insert(address_name, self.clone());
+            return Ok(ComptimeValueType::Void);
+        }
+        let return_type = self.return_type(compiler)?;
+        self.compile_body(compiler, address_name, return_type)?;
+        Ok(ComptimeValueType::Void)
     }
 
     fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
-
+        writeln!(f, "{}fnc {} (doc: {:?})", indent_fn(indent), self.address_name(), self.doc)?;
+        for stmt in &self.body {
+            stmt.fmt_with_indent(f, indent + 1)?;
+        }
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn calls_function(&self) -> bool {
+        true
+    }
 }
 
 impl Debug for FunctionDefineNode {
@@ -46,6 +243,8 @@ impl Debug for FunctionDefineNode {
             .field("id", &self.id)
             .field("body", &self.body)
             .field("return_type", &self.return_type)
+            .field("doc", &self.doc)
+            .field("type_params", &self.type_params)
             .finish()
     }
 }
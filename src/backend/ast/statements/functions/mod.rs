@@ -1,2 +1,4 @@
 pub mod args_node;
 pub mod function_nodes;
+pub mod return_statement;
+pub mod closures;
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    compiler::{
+        byte_code::{Compilable, Compiler, indent_fn},
+        comptime_variable_checker::comptime_value_for_check::ComptimeValueType,
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+#[derive(Clone)]
+pub struct ReturnStatement {
+    pub value: Option<Box<dyn Compilable>>,
+    pub span: Span,
+}
+
+impl Compilable for ReturnStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let expected = compiler
+            .return_types
+            .last()
+            .cloned()
+            .ok_or_else(|| CompileError::ReturnOutsideFunction {
+                span: self.span.clone(),
+            })?;
+        let found = match &self.value {
+            Some(value) => value.compile(compiler)?,
+            None => ComptimeValueType::Void,
+        };
+        if !expected.accepts(&found) {
+            return Err(CompileError::TypeMismatch {
+                expected,
+                found,
+                span: self
+                    .value
+                    .as_ref()
+                    .map_or(&self.span, |value| value.span())
+                    .clone(),
+            });
+        }
+        compiler.out.push(if found == ComptimeValueType::Void {
+            Instructions::Return
+        } else {
+            Instructions::ReturnValue
+        });
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}return", indent_fn(indent))?;
+        if let Some(value) = &self.value {
+            value.fmt_with_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        self.value.iter().map(|value| value.as_ref()).collect()
+    }
+    fn always_returns(&self) -> bool {
+        true
+    }
+}
+
+impl Debug for ReturnStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
@@ -1,37 +1,85 @@
 use crate::backend::{
-    compiler::{ byte_code::{Compilable, Compiler, indent_fn}, comptime_variable_checker::comptime_value_for_check::ComptimeValueType, instructions::Instructions},
+    compiler::{ byte_code::{Compilable, Compiler, indent_fn, nodes}, comptime_variable_checker::comptime_value_for_check::ComptimeValueType, instructions::Instructions},
     errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
 };
 use std::fmt::Debug;
 #[derive(Clone)]
 pub struct IfStatement {
     pub then_branch: Vec<Box<dyn Compilable>>,
     pub condition: Box<dyn Compilable>,
+    pub else_if_branches: Vec<IfBranch>,
     pub else_branch: Option<Vec<Box<dyn Compilable>>>,
+    pub span: Span,
 }
 
-impl Compilable for IfStatement {
-    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        let cond_type = self.condition.compile(compiler)?;
-        if cond_type != ComptimeValueType::Bool {
-            return Err(CompileError::TypeMismatch {
-                expected: ComptimeValueType::Bool,
-                found: cond_type,
-            });
+#[derive(Clone, Debug)]
+pub struct IfBranch {
+    pub condition: Box<dyn Compilable>,
+    pub body: Vec<Box<dyn Compilable>>,
+    pub span: Span,
+}
+
+/// Compiles one `condition { body }` arm and returns the position of its
+/// placeholder jump to the end of the whole if chain.
+fn compile_arm(
+    compiler: &mut Compiler,
+    condition: &dyn Compilable,
+    body: &[Box<dyn Compilable>],
+) -> Result<usize, CompileError> {
+    let cond_type = condition.compile(compiler)?;
+    if cond_type != ComptimeValueType::Bool {
+        return Err(CompileError::TypeMismatch {
+            expected: ComptimeValueType::Bool,
+            found: cond_type,
+            span: condition.span().clone(),
+        });
+    }
+
+    let jump_if_false_pos = compiler.out.len();
+    compiler.out.push(Instructions::JumpIfFalse(0)); // Placeholder for jump instruction
+    // Inside `if (x != undef)` the optional `x` has the type of its value
+    let narrowed = compiler.narrow(condition.defined_when(true), &nodes(body));
+    compiler.context.enter_scope();
+    for stmt in body {
+        stmt.compile(compiler)?;
+    }
+    compiler.context.exit_scope();
+    compiler.restore(narrowed);
+    let jump_end_pos = compiler.out.len();
+    compiler.out.push(Instructions::Jump(0)); // Placeholder for end jump instruction
+
+    let next_arm = compiler.out.len();
+    compiler.out[jump_if_false_pos] = Instructions::JumpIfFalse(next_arm); // If false, try the next arm
+    Ok(jump_end_pos)
+}
+
+impl IfStatement {
+    /// Conditions and bodies of the `else if` arms from `arm` on and the `else` branch,
+    /// the code that runs when the earlier conditions were false.
+    fn code_after(&self, arm: usize) -> Vec<&dyn Compilable> {
+        let mut code = Vec::new();
+        for branch in &self.else_if_branches[arm..] {
+            code.push(branch.condition.as_ref());
+            code.extend(nodes(&branch.body));
         }
+        if let Some(else_branch) = &self.else_branch {
+            code.extend(nodes(else_branch));
+        }
+        code
+    }
+}
 
-        let jump_if_false_pos = compiler.out.len();
-        compiler.out.push(Instructions::JumpIfFalse(0)); // Placeholder for jump instruction
-        compiler.context.enter_scope();
-        for stmt in &self.then_branch {
-            stmt.compile(compiler)?;
+impl Compilable for IfStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let mut end_jumps = vec![compile_arm(compiler, self.condition.as_ref(), &self.then_branch)?];
+        // Later arms only run when the earlier conditions were false
+        let mut narrowed = compiler.narrow(self.condition.defined_when(false), &self.code_after(0));
+        for (idx, branch) in self.else_if_branches.iter().enumerate() {
+            end_jumps.push(compile_arm(compiler, branch.condition.as_ref(), &branch.body)?);
+            narrowed.extend(compiler.narrow(branch.condition.defined_when(false), &self.code_after(idx + 1)));
         }
-        compiler.context.exit_scope();
-        let jump_end_pos = compiler.out.len();
-        compiler.out.push(Instructions::Jump(0)); // Placeholder for end jump instruction
 
-        let else_start = compiler.out.len();
-        compiler.out[jump_if_false_pos] = Instructions::JumpIfFalse(else_start); // If false, jump to else_start
         compiler.context.enter_scope();
         if let Some(else_branch) = &self.else_branch {
             for stmt in else_branch {
@@ -39,9 +87,13 @@ impl Compilable for IfStatement {
             }
         }
         compiler.context.exit_scope();
+        compiler.restore(narrowed);
 
+        // Every arm jumps to the same end label
         let end = compiler.out.len();
-        compiler.out[jump_end_pos] = Instructions::Jump(end); // Jump to end
+        for jump_end_pos in end_jumps {
+            compiler.out[jump_end_pos] = Instructions::Jump(end);
+        }
 
         Ok(ComptimeValueType::Void) // void
     }
@@ -52,8 +104,43 @@ impl Compilable for IfStatement {
             self.then_branch[i].fmt_with_indent(f, indent)?;
             i += 1;
         }
+        for branch in &self.else_if_branches {
+            writeln!(f, "{}else if(...)", indent_fn(indent))?;
+            for stmt in &branch.body {
+                stmt.fmt_with_indent(f, indent)?;
+            }
+        }
+        if let Some(else_branch) = &self.else_branch {
+            writeln!(f, "{}else", indent_fn(indent))?;
+            for stmt in else_branch {
+                stmt.fmt_with_indent(f, indent)?;
+            }
+        }
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.condition.as_ref()];
+        children.extend(nodes(&self.then_branch));
+        children.extend(self.code_after(0));
+        children
+    }
+    fn breaks_out(&self, label: Option<&str>, innermost: bool) -> bool {
+        let breaks = |body: &[Box<dyn Compilable>]| {
+            body.iter().any(|stmt| stmt.breaks_out(label, innermost))
+        };
+        breaks(&self.then_branch)
+            || self.else_if_branches.iter().any(|branch| breaks(&branch.body))
+            || self.else_branch.as_ref().is_some_and(|body| breaks(body))
+    }
+    fn always_returns(&self) -> bool {
+        let returns = |body: &[Box<dyn Compilable>]| body.iter().any(|stmt| stmt.always_returns());
+        self.else_branch.as_ref().is_some_and(|body| returns(body))
+            && returns(&self.then_branch)
+            && self.else_if_branches.iter().all(|branch| returns(&branch.body))
+    }
 }
 
 impl Debug for IfStatement {
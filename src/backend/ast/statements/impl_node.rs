@@ -0,0 +1,172 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    ast::{
+        nodes::FunctionCallNode,
+        statements::{
+            enums::{compile_variant, enum_receiver},
+            functions::function_nodes::FunctionDefineNode,
+        },
+    },
+    compiler::{
+        byte_code::{check_defined, indent_fn, Compilable, Compiler},
+        comptime_variable_checker::comptime_value_for_check::ComptimeValueType,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+/*
+ * Impl node
+ */
+#[derive(Clone)]
+pub struct ImplNode {
+    pub type_name: String,
+    pub methods: Vec<FunctionDefineNode>,
+    pub span: Span,
+}
+
+impl ImplNode {
+    /// Methods can only be added to structs and enums.
+    fn check_type(&self, compiler: &Compiler) -> Result<(), CompileError> {
+        match compiler.context.get_type(&self.type_name, &self.span)? {
+            ComptimeValueType::Struct(_) | ComptimeValueType::Enum(_) => Ok(()),
+            _ => Err(CompileError::UndefinedType {
+                undefined_type: self.type_name.clone(),
+                span: self.span.clone(),
+            }),
+        }
+    }
+}
+
+impl Compilable for ImplNode {
+    fn declare(&self, compiler: &mut Compiler) -> Result<(), CompileError> {
+        self.check_type(compiler)?;
+        for method in &self.methods {
+            method.declare(compiler)?;
+        }
+        Ok(())
+    }
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        self.check_type(compiler)?;
+        for method in &self.methods {
+            method.compile(compiler)?;
+        }
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Impl {}", indent_fn(indent), self.type_name)?;
+        for method in &self.methods {
+            method.fmt_with_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn calls_function(&self) -> bool {
+        true
+    }
+}
+impl Debug for ImplNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+/*
+ * Method call node
+ */
+#[derive(Clone)]
+pub struct MethodCallNode {
+    pub object: Box<dyn Compilable>,
+    /// Name of `object` when it is a bare identifier, which may be a type
+    pub receiver_name: Option<String>,
+    /// Method name and arguments, without `self`
+    pub call: FunctionCallNode,
+    pub span: Span,
+}
+
+impl MethodCallNode {
+    /// Type name of a call like `Point.new()`, variables shadow types.
+    fn static_type(&self, compiler: &Compiler) -> Option<String> {
+        self.receiver_name
+            .clone()
+            .filter(|name| compiler.context.get_variable(name).is_none())
+            .filter(|name| {
+                compiler.context.structs.contains_key(name) || compiler.context.enums.contains_key(name)
+            })
+    }
+}
+
+impl Compilable for MethodCallNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let name = &self.call.name;
+        // `Shape.Circle(2.0)` builds a variant, names that are no function of `Shape` either are unknown variants
+        if let Some(enum_name) = enum_receiver(compiler, self.receiver_name.as_ref()) {
+            let is_variant = compiler.context.get_enum(&enum_name, &self.span)?.variant(name).is_some();
+            if is_variant || compiler.context.get_method(&enum_name, name, &self.span).is_err() {
+                return compile_variant(compiler, &enum_name, name, Some(&self.call), &self.span);
+            }
+        }
+        let (type_name, method) = if let Some(type_name) = self.static_type(compiler) {
+            let method = compiler.context.get_method(&type_name, name, &self.call.span)?;
+            if method.takes_self() {
+                return Err(CompileError::MethodCalledOnType {
+                    type_name,
+                    name: name.clone(),
+                    span: self.span.clone(),
+                });
+            }
+            (type_name, method)
+        } else {
+            // The receiver is passed as the implicit first argument
+            let object_type = self.object.compile(compiler)?;
+            check_defined(&object_type, self.object.span())?;
+            let type_name = match object_type {
+                ComptimeValueType::Struct(type_name) | ComptimeValueType::Enum(type_name) => type_name,
+                other => {
+                    return Err(CompileError::UnknownMethod {
+                        type_name: format!("{:?}", other),
+                        name: name.clone(),
+                        span: self.call.span.clone(),
+                    });
+                }
+            };
+            let method = compiler.context.get_method(&type_name, name, &self.call.span)?;
+            if !method.takes_self() {
+                return Err(CompileError::StaticCalledOnValue {
+                    type_name,
+                    name: name.clone(),
+                    span: self.span.clone(),
+                });
+            }
+            (type_name, method)
+        };
+        let explicit = if method.takes_self() { &method.args[1..] } else { &method.args[..] };
+        let address_name = format!("{}.{}", type_name, name);
+        self.call.compile_arguments(compiler, &address_name, explicit)?;
+        compiler.call_function(&address_name, method.args.len());
+        Ok(method.return_type)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Method({})", indent_fn(indent), self.call.name)?;
+        self.object.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.object.as_ref()];
+        children.extend(self.call.children());
+        children
+    }
+    fn calls_function(&self) -> bool {
+        true
+    }
+}
+impl Debug for MethodCallNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    compiler::{
+        byte_code::{Compilable, Compiler, indent_fn, nodes},
+        comptime_variable_checker::comptime_value_for_check::ComptimeValueType,
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+/// Infinite `loop { }`, left only through `break`.
+#[derive(Clone)]
+pub struct LoopStatement {
+    pub body: Vec<Box<dyn Compilable>>,
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+impl Compilable for LoopStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let start = compiler.out.len();
+        compiler.enter_loop(self.label.clone());
+        compiler.context.enter_scope();
+        for stmt in &self.body {
+            stmt.compile(compiler)?;
+        }
+        compiler.context.exit_scope();
+        compiler.out.push(Instructions::Jump(start));
+        let end = compiler.out.len();
+        compiler.exit_loop(start, end);
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}loop", indent_fn(indent))?;
+        for stmt in &self.body {
+            stmt.fmt_with_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        nodes(&self.body)
+    }
+    fn always_returns(&self) -> bool {
+        // Without a `break` the loop can only be left by `return` or `throw`
+        !self
+            .body
+            .iter()
+            .any(|stmt| stmt.breaks_out(self.label.as_deref(), true))
+    }
+    fn breaks_out(&self, label: Option<&str>, _innermost: bool) -> bool {
+        // A loop with the same label catches the labeled breaks inside of it
+        label.is_some() && label != self.label.as_deref()
+            && self.body.iter().any(|stmt| stmt.breaks_out(label, false))
+    }
+}
+
+impl Debug for LoopStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+#[derive(Clone)]
+pub struct BreakStatement {
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+impl Compilable for BreakStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let try_depth = compiler.get_loop(&self.label, "break", &self.span)?.try_depth;
+        compiler.leave_tries(try_depth);
+        let pos = compiler.out.len();
+        compiler
+            .get_loop(&self.label, "break", &self.span)?
+            .break_jumps
+            .push(pos);
+        compiler.out.push(Instructions::Jump(0)); // Patched when the loop ends
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}break {:?}", indent_fn(indent), self.label)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn breaks_out(&self, label: Option<&str>, innermost: bool) -> bool {
+        match &self.label {
+            Some(own) => label == Some(own.as_str()),
+            None => innermost,
+        }
+    }
+}
+
+impl Debug for BreakStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+#[derive(Clone)]
+pub struct ContinueStatement {
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+impl Compilable for ContinueStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let try_depth = compiler.get_loop(&self.label, "continue", &self.span)?.try_depth;
+        compiler.leave_tries(try_depth);
+        let pos = compiler.out.len();
+        compiler
+            .get_loop(&self.label, "continue", &self.span)?
+            .continue_jumps
+            .push(pos);
+        compiler.out.push(Instructions::Jump(0)); // Patched when the loop ends
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}continue {:?}", indent_fn(indent), self.label)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+impl Debug for ContinueStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    ast::statements::enums::find_variant,
+    compiler::{
+        byte_code::{Compilable, Compiler, indent_fn, nodes},
+        comptime_variable_checker::{
+            comptime_context::ComptimeVariable,
+            comptime_value_for_check::ComptimeValueType::{
+                self, Bool, Enum, Float, Int, StringValue,
+            },
+        },
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+#[derive(Clone, Debug)]
+pub enum MatchPattern {
+    /// A number, float or string literal compared with `==`
+    Value(Box<dyn Compilable>),
+    Bool(bool, Span),
+    Range {
+        start: Box<dyn Compilable>,
+        end: Box<dyn Compilable>,
+        inclusive: bool,
+    },
+    /// `Shape.Circle(r)`, the bindings name the payload values, `_` skips one
+    Variant {
+        enum_name: String,
+        variant: String,
+        bindings: Vec<String>,
+        span: Span,
+    },
+    Wildcard(Span),
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchArm {
+    pub pattern: MatchPattern,
+    pub body: Vec<Box<dyn Compilable>>,
+    pub span: Span,
+}
+
+#[derive(Clone)]
+pub struct MatchStatement {
+    pub value: Box<dyn Compilable>,
+    pub arms: Vec<MatchArm>,
+    pub span: Span,
+}
+
+/// Compiles one side of a pattern and checks it against the matched value's type.
+fn compile_pattern_value(
+    compiler: &mut Compiler,
+    pattern: &dyn Compilable,
+    value_type: &ComptimeValueType,
+) -> Result<(), CompileError> {
+    let found = pattern.compile(compiler)?;
+    match (value_type, &found) {
+        (Int, Int) | (Float, Float) | (Float, Int) | (StringValue, StringValue) => Ok(()),
+        _ => Err(CompileError::TypeMismatch {
+            expected: value_type.clone(),
+            found,
+            span: pattern.span().clone(),
+        }),
+    }
+}
+
+impl MatchStatement {
+    fn check_exhaustive(
+        &self,
+        compiler: &Compiler,
+        value_type: &ComptimeValueType,
+    ) -> Result<(), CompileError> {
+        if self
+            .arms
+            .iter()
+            .any(|arm| matches!(arm.pattern, MatchPattern::Wildcard(_)))
+        {
+            return Ok(());
+        }
+        if let Enum(enum_name) = value_type {
+            let definition = compiler.context.get_enum(enum_name, &self.span)?;
+            for missing in &definition.variants {
+                let covered = self.arms.iter().any(
+                    |arm| matches!(&arm.pattern, MatchPattern::Variant { variant, .. } if *variant == missing.name),
+                );
+                if !covered {
+                    return Err(CompileError::NonExhaustiveMatch {
+                        missing: format!("{}.{}", enum_name, missing.name),
+                        span: self.span.clone(),
+                    });
+                }
+            }
+            return Ok(());
+        }
+        if *value_type != Bool {
+            return Ok(());
+        }
+        for missing in [true, false] {
+            let covered = self
+                .arms
+                .iter()
+                .any(|arm| matches!(arm.pattern, MatchPattern::Bool(b, _) if b == missing));
+            if !covered {
+                return Err(CompileError::NonExhaustiveMatch {
+                    missing: missing.to_string(),
+                    span: self.span.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Compilable for MatchStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let value_type = self.value.compile(compiler)?;
+        if !matches!(value_type, Int | Float | StringValue | Bool | Enum(_)) {
+            return Err(CompileError::TypeMismatch {
+                expected: Int,
+                found: value_type,
+                span: self.value.span().clone(),
+            });
+        }
+        self.check_exhaustive(compiler, &value_type)?;
+
+        // The matched value is evaluated once and compared from a hidden variable
+        let value_var = compiler.temp_variable("match");
+        compiler.out.push(Instructions::SaveVar(value_var.clone()));
+
+        let mut end_jumps = Vec::new();
+        for arm in &self.arms {
+            let mut next_arm_jumps = Vec::new();
+            // Payload values to store in the arm's scope, with their position and type
+            let mut bindings = Vec::new();
+            match &arm.pattern {
+                MatchPattern::Value(pattern) => {
+                    compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                    compile_pattern_value(compiler, pattern.as_ref(), &value_type)?;
+                    compiler.out.push(Instructions::Equal);
+                    next_arm_jumps.push(compiler.out.len());
+                    compiler.out.push(Instructions::JumpIfFalse(0));
+                }
+                MatchPattern::Bool(value, span) => {
+                    if value_type != Bool {
+                        return Err(CompileError::TypeMismatch {
+                            expected: value_type,
+                            found: Bool,
+                            span: span.clone(),
+                        });
+                    }
+                    compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                    compiler.out.push(Instructions::PushBool(*value));
+                    compiler.out.push(Instructions::Equal);
+                    next_arm_jumps.push(compiler.out.len());
+                    compiler.out.push(Instructions::JumpIfFalse(0));
+                }
+                MatchPattern::Range {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    if !matches!(value_type, Int | Float) {
+                        return Err(CompileError::TypeMismatch {
+                            expected: Int,
+                            found: value_type,
+                            span: arm.span.clone(),
+                        });
+                    }
+                    compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                    compile_pattern_value(compiler, start.as_ref(), &value_type)?;
+                    compiler.out.push(Instructions::GreaterEqual);
+                    next_arm_jumps.push(compiler.out.len());
+                    compiler.out.push(Instructions::JumpIfFalse(0));
+
+                    compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                    compile_pattern_value(compiler, end.as_ref(), &value_type)?;
+                    compiler.out.push(if *inclusive {
+                        Instructions::LessEqual
+                    } else {
+                        Instructions::LessThan
+                    });
+                    next_arm_jumps.push(compiler.out.len());
+                    compiler.out.push(Instructions::JumpIfFalse(0));
+                }
+                MatchPattern::Variant {
+                    enum_name,
+                    variant,
+                    bindings: names,
+                    span,
+                } => {
+                    let found = Enum(enum_name.clone());
+                    if value_type != found {
+                        return Err(CompileError::TypeMismatch {
+                            expected: value_type,
+                            found,
+                            span: span.clone(),
+                        });
+                    }
+                    let (tag, payload) = find_variant(compiler, enum_name, variant, span)?;
+                    if names.len() != payload.len() {
+                        return Err(CompileError::VariantPayloadCount {
+                            variant: format!("{}.{}", enum_name, variant),
+                            expected: payload.len(),
+                            found: names.len(),
+                            span: span.clone(),
+                        });
+                    }
+                    compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                    compiler.out.push(Instructions::IsVariant(tag));
+                    next_arm_jumps.push(compiler.out.len());
+                    compiler.out.push(Instructions::JumpIfFalse(0));
+                    bindings = names
+                        .iter()
+                        .zip(payload)
+                        .enumerate()
+                        .filter(|(_, (name, _))| *name != "_")
+                        .map(|(idx, (name, payload_type))| (name.clone(), idx, payload_type))
+                        .collect();
+                }
+                MatchPattern::Wildcard(_) => {}
+            }
+
+            compiler.context.enter_scope();
+            for (name, idx, payload_type) in bindings {
+                compiler.context.add_variable(
+                    name.clone(),
+                    ComptimeVariable {
+                        value_type: payload_type,
+                        is_const: false,
+                        storage: None,
+                    },
+                    &arm.span,
+                )?;
+                compiler.out.push(Instructions::LoadVar(value_var.clone()));
+                compiler.out.push(Instructions::LoadPayload(idx));
+                compiler.save_variable(&name);
+            }
+            for stmt in &arm.body {
+                stmt.compile(compiler)?;
+            }
+            compiler.context.exit_scope();
+            end_jumps.push(compiler.out.len());
+            compiler.out.push(Instructions::Jump(0)); // Placeholder for end jump instruction
+
+            let next_arm = compiler.out.len();
+            for pos in next_arm_jumps {
+                compiler.out[pos] = Instructions::JumpIfFalse(next_arm);
+            }
+        }
+
+        let end = compiler.out.len();
+        for pos in end_jumps {
+            compiler.out[pos] = Instructions::Jump(end);
+        }
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}match", indent_fn(indent))?;
+        self.value.fmt_with_indent(f, indent + 2)?;
+        for arm in &self.arms {
+            writeln!(f, "{}{:?} =>", indent_fn(indent + 1), arm.pattern)?;
+            for stmt in &arm.body {
+                stmt.fmt_with_indent(f, indent + 2)?;
+            }
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.value.as_ref()];
+        for arm in &self.arms {
+            children.extend(nodes(&arm.body));
+        }
+        children
+    }
+    fn breaks_out(&self, label: Option<&str>, innermost: bool) -> bool {
+        self.arms
+            .iter()
+            .any(|arm| arm.body.iter().any(|stmt| stmt.breaks_out(label, innermost)))
+    }
+    fn always_returns(&self) -> bool {
+        let has_arm = |pattern: fn(&MatchPattern) -> bool| {
+            self.arms.iter().any(|arm| pattern(&arm.pattern))
+        };
+        // A match on an enum only compiles when it covers every variant
+        let exhaustive = has_arm(|p| matches!(p, MatchPattern::Wildcard(_)))
+            || (has_arm(|p| matches!(p, MatchPattern::Bool(true, _)))
+                && has_arm(|p| matches!(p, MatchPattern::Bool(false, _))))
+            || has_arm(|p| matches!(p, MatchPattern::Variant { .. }));
+        exhaustive
+            && self
+                .arms
+                .iter()
+                .all(|arm| arm.body.iter().any(|stmt| stmt.always_returns()))
+    }
+}
+
+impl Debug for MatchStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
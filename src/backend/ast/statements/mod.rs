@@ -1,4 +1,10 @@
+pub mod for_statement;
 pub mod functions;
 pub mod if_statement;
+pub mod impl_node;
+pub mod loop_statement;
+pub mod match_statement;
 pub mod while_statement;
 pub mod structs;
+pub mod enums;
+pub mod try_statement;
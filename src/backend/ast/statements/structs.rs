@@ -1,25 +1,68 @@
+use std::collections::HashSet;
 use std::fmt::Debug;
 
-use crate::backend::compiler::byte_code::Compilable;
+use crate::backend::ast::statements::enums::{compile_variant, enum_receiver};
+use crate::backend::compiler::byte_code::{check_defined, indent_fn, Compilable};
+use crate::backend::compiler::assign_target::{AssignTarget, PathStep};
 use crate::backend::compiler::byte_code::Compiler;
 use crate::backend::compiler::comptime_variable_checker::comptime_value_for_check::ComptimeValueType;
+use crate::backend::compiler::instructions::Instructions;
+use crate::backend::compiler::structs_compiler_context::CompileTimeStructForCheck;
 use crate::backend::errors::compiler::compiler_errors::CompileError;
-#[derive(Clone)]
+use crate::backend::lexer::tokens::Span;
+#[derive(Clone, Debug)]
 pub struct StructVariable{
-    pub var_type:String
+    pub name:String,
+    pub var_type:String,
+    pub span:Span
 }
 
 #[derive(Clone)]
 pub struct StructDefineNode{
-    pub args:Vec<StructVariable>
+    pub name:String,
+    pub args:Vec<StructVariable>,
+    pub span:Span
 }
 
 impl Compilable for StructDefineNode{
+    fn declare_types(&self, compiler: &mut Compiler) -> Result<(), CompileError> {
+        let mut seen = HashSet::new();
+        for field in &self.args {
+            if !seen.insert(&field.name) {
+                return Err(CompileError::DuplicateField {
+                    field: field.name.clone(),
+                    span: field.span.clone(),
+                });
+            }
+        }
+        compiler.context.add_struct(
+            self.name.clone(),
+            CompileTimeStructForCheck {
+                fields: self.args.clone(),
+            },
+            &self.span,
+        )
+    }
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType,CompileError>{
-        todo!()
+        // Structs outside of the top level are not hoisted
+        if !compiler.context.structs.contains_key(&self.name) {
+            self.declare_types(compiler)?;
+        }
+        // Field types are checked once every struct is known
+        for field in &self.args {
+            compiler.context.get_type(&field.var_type, &field.span)?;
+        }
+        Ok(ComptimeValueType::Void)
     }
     fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
-        todo!()
+        writeln!(f, "{}Struct {}", indent_fn(indent), self.name)?;
+        for field in &self.args {
+            writeln!(f, "{}{}: {}", indent_fn(indent + 1), field.name, field.var_type)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
     }
 }
 impl Debug for StructDefineNode{
@@ -28,6 +71,161 @@ impl Debug for StructDefineNode{
     }
 }
 
+/// Index and type of `field` in a value of type `value_type`.
+pub fn find_field(
+    compiler: &Compiler,
+    value_type: &ComptimeValueType,
+    field: &str,
+    span: &Span,
+) -> Result<(usize, ComptimeValueType), CompileError> {
+    let unknown = |type_name: String| CompileError::UnknownField {
+        type_name,
+        field: field.to_string(),
+        span: span.clone(),
+    };
+    check_defined(value_type, span)?;
+    // Tuple elements are numbered, `t.0`
+    if let ComptimeValueType::Tuple(elements) = value_type {
+        return field
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| Some((idx, elements.get(idx)?.clone())))
+            .ok_or_else(|| unknown(format!("{:?}", value_type)));
+    }
+    let ComptimeValueType::Struct(name) = value_type else {
+        return Err(unknown(format!("{:?}", value_type)));
+    };
+    let definition = compiler.context.get_struct(name, span)?;
+    let (idx, field) = definition
+        .field(field)
+        .ok_or_else(|| unknown(name.clone()))?;
+    Ok((idx, compiler.context.get_type(&field.var_type, &field.span)?))
+}
+
+/*
+ * Struct literal node
+ */
+#[derive(Clone, Debug)]
+pub struct StructFieldValue{
+    pub name:String,
+    pub value:Box<dyn Compilable>,
+    pub span:Span
+}
+
+#[derive(Clone)]
+pub struct StructLiteralNode{
+    pub name:String,
+    pub fields:Vec<StructFieldValue>,
+    pub span:Span
+}
+
+impl Compilable for StructLiteralNode{
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType,CompileError>{
+        let definition = compiler.context.get_struct(&self.name, &self.span)?;
+        for (idx, given) in self.fields.iter().enumerate() {
+            if definition.field(&given.name).is_none() {
+                return Err(CompileError::UnknownField {
+                    type_name: self.name.clone(),
+                    field: given.name.clone(),
+                    span: given.span.clone(),
+                });
+            }
+            if self.fields[..idx].iter().any(|f| f.name == given.name) {
+                return Err(CompileError::DuplicateField {
+                    field: given.name.clone(),
+                    span: given.span.clone(),
+                });
+            }
+        }
+        // Values are pushed in definition order, whatever order they are written in
+        for field in &definition.fields {
+            let given = self
+                .fields
+                .iter()
+                .find(|f| f.name == field.name)
+                .ok_or_else(|| CompileError::MissingField {
+                    struct_name: self.name.clone(),
+                    field: field.name.clone(),
+                    span: self.span.clone(),
+                })?;
+            let expected = compiler.context.get_type(&field.var_type, &field.span)?;
+            let found = given.value.compile(compiler)?;
+            if !expected.accepts(&found) {
+                return Err(CompileError::TypeMismatch {
+                    expected,
+                    found,
+                    span: given.value.span().clone(),
+                });
+            }
+        }
+        compiler
+            .out
+            .push(Instructions::MakeStruct(self.name.clone(), definition.fields.len()));
+        Ok(ComptimeValueType::Struct(self.name.clone()))
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}{} {{", indent_fn(indent), self.name)?;
+        for field in &self.fields {
+            writeln!(f, "{}{}:", indent_fn(indent + 1), field.name)?;
+            field.value.fmt_with_indent(f, indent + 2)?;
+        }
+        writeln!(f, "{}}}", indent_fn(indent))
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        self.fields.iter().map(|field| field.value.as_ref()).collect()
+    }
+}
+impl Debug for StructLiteralNode{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
 /*
- * Sruct acces node
+ * Struct access node
  */
+#[derive(Clone)]
+pub struct FieldAccessNode{
+    pub object:Box<dyn Compilable>,
+    /// Name of `object` when it is a bare identifier, which may be an enum
+    pub receiver_name:Option<String>,
+    pub field:String,
+    pub span:Span
+}
+
+impl Compilable for FieldAccessNode{
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType,CompileError>{
+        // `Shape.Empty` builds a variant without a payload
+        if let Some(enum_name) = enum_receiver(compiler, self.receiver_name.as_ref()) {
+            return compile_variant(compiler, &enum_name, &self.field, None, &self.span);
+        }
+        let object_type = self.object.compile(compiler)?;
+        let (idx, field_type) = find_field(compiler, &object_type, &self.field, &self.span)?;
+        compiler.out.push(Instructions::LoadField(idx));
+        Ok(field_type)
+    }
+    fn assign_target(&self) -> Option<AssignTarget> {
+        let mut target = self.object.assign_target()?;
+        target.path.push(PathStep::Field(self.field.clone()));
+        target.span = self.span.clone();
+        Some(target)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}Field({})", indent_fn(indent), self.field)?;
+        self.object.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.object.as_ref()]
+    }
+}
+impl Debug for FieldAccessNode{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Debug;
+
+use crate::backend::{
+    compiler::{
+        byte_code::{Compilable, Compiler, indent_fn, nodes},
+        comptime_variable_checker::{
+            comptime_context::ComptimeVariable,
+            comptime_value_for_check::ComptimeValueType,
+        },
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+/*
+ * Try statement
+ */
+#[derive(Clone)]
+pub struct TryStatement {
+    pub try_body: Vec<Box<dyn Compilable>>,
+    /// Name the error message is bound to in `catch (e)`
+    pub error_name: Option<String>,
+    pub catch_body: Vec<Box<dyn Compilable>>,
+    pub span: Span,
+}
+
+impl Compilable for TryStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let handler_pos = compiler.out.len();
+        compiler.out.push(Instructions::PushHandler(0)); // Patched once the catch block starts
+        compiler.try_depth += 1;
+        compiler.context.enter_scope();
+        for stmt in &self.try_body {
+            stmt.compile(compiler)?;
+        }
+        compiler.context.exit_scope();
+        compiler.try_depth -= 1;
+        compiler.out.push(Instructions::PopHandler);
+        let jump_end_pos = compiler.out.len();
+        compiler.out.push(Instructions::Jump(0)); // Placeholder for end jump instruction
+
+        // The VM pushes the error message before jumping here
+        let catch_start = compiler.out.len();
+        compiler.out[handler_pos] = Instructions::PushHandler(catch_start);
+        compiler.context.enter_scope();
+        match &self.error_name {
+            Some(name) => {
+                compiler.context.add_variable(
+                    name.clone(),
+                    ComptimeVariable {
+                        value_type: ComptimeValueType::StringValue,
+                        is_const: false,
+                        storage: None,
+                    },
+                    &self.span,
+                )?;
+                compiler.out.push(Instructions::SaveVar(name.clone()));
+            }
+            None => compiler.out.push(Instructions::Pop),
+        }
+        for stmt in &self.catch_body {
+            stmt.compile(compiler)?;
+        }
+        compiler.context.exit_scope();
+
+        let end = compiler.out.len();
+        compiler.out[jump_end_pos] = Instructions::Jump(end);
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}try", indent_fn(indent))?;
+        for stmt in &self.try_body {
+            stmt.fmt_with_indent(f, indent + 1)?;
+        }
+        writeln!(f, "{}catch({})", indent_fn(indent), self.error_name.as_deref().unwrap_or(""))?;
+        for stmt in &self.catch_body {
+            stmt.fmt_with_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = nodes(&self.try_body);
+        children.extend(nodes(&self.catch_body));
+        children
+    }
+    fn breaks_out(&self, label: Option<&str>, innermost: bool) -> bool {
+        self.try_body
+            .iter()
+            .chain(&self.catch_body)
+            .any(|stmt| stmt.breaks_out(label, innermost))
+    }
+    fn always_returns(&self) -> bool {
+        let returns = |body: &[Box<dyn Compilable>]| body.iter().any(|stmt| stmt.always_returns());
+        returns(&self.try_body) && returns(&self.catch_body)
+    }
+}
+
+impl Debug for TryStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
+
+/*
+ * Throw statement
+ */
+#[derive(Clone)]
+pub struct ThrowStatement {
+    pub value: Box<dyn Compilable>,
+    pub span: Span,
+}
+
+impl Compilable for ThrowStatement {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let found = self.value.compile(compiler)?;
+        if found != ComptimeValueType::StringValue {
+            return Err(CompileError::TypeMismatch {
+                expected: ComptimeValueType::StringValue,
+                found,
+                span: self.value.span().clone(),
+            });
+        }
+        compiler.out.push(Instructions::Throw);
+        Ok(ComptimeValueType::Void)
+    }
+    fn fmt_with_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}throw", indent_fn(indent))?;
+        self.value.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.value.as_ref()]
+    }
+    fn always_returns(&self) -> bool {
+        // Like after a `return`, nothing after a `throw` runs
+        true
+    }
+}
+
+impl Debug for ThrowStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
@@ -2,15 +2,18 @@ use std::fmt::Debug;
 
 use crate::backend::{
     compiler::{
-        byte_code::{self, Compilable}, comptime_variable_checker::comptime_value_for_check::ComptimeValueType::{self}, instructions::Instructions
+        byte_code::{self, Compilable, nodes}, comptime_variable_checker::comptime_value_for_check::ComptimeValueType::{self}, instructions::Instructions
     },
     errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
 };
 
 #[derive(Clone)]
 pub struct WhileStatement {
     pub condition: Box<dyn Compilable>,
     pub body: Vec<Box<dyn Compilable>>,
+    pub label: Option<String>,
+    pub span: Span,
 }
 
 impl Compilable for WhileStatement {
@@ -23,21 +26,26 @@ impl Compilable for WhileStatement {
             return Err(CompileError::TypeMismatch {
                 expected: ComptimeValueType::Bool,
                 found: cond_type,
+                span: self.condition.span().clone(),
             });
         }
         let jump_if_false_pos = compiler.out.len();
         compiler.out.push(Instructions::JumpIfFalse(0));
         let statements_start = compiler.out.len();
+        compiler.enter_loop(self.label.clone());
         compiler.context.enter_scope();
         for statemnt in &self.body {
             statemnt.compile(compiler)?;
         }
         compiler.context.exit_scope();
+        let condition_start = compiler.out.len();
         self.condition.compile(compiler)?;
         compiler
             .out
             .push(Instructions::JumpIfTrue(statements_start));
-        compiler.out[jump_if_false_pos] = Instructions::JumpIfFalse(compiler.out.len());
+        let end = compiler.out.len();
+        compiler.out[jump_if_false_pos] = Instructions::JumpIfFalse(end);
+        compiler.exit_loop(condition_start, end);
         Ok(ComptimeValueType::Void)
     }
     fn fmt_with_indent(
@@ -47,6 +55,18 @@ impl Compilable for WhileStatement {
     ) -> std::fmt::Result {
         writeln!(_f, "if")
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = vec![self.condition.as_ref()];
+        children.extend(nodes(&self.body));
+        children
+    }
+    fn breaks_out(&self, label: Option<&str>, _innermost: bool) -> bool {
+        label.is_some() && label != self.label.as_deref()
+            && self.body.iter().any(|stmt| stmt.breaks_out(label, false))
+    }
 }
 
 impl Debug for WhileStatement {
@@ -1,8 +1,10 @@
 use crate::backend::buildin_macros::macros::{
-    Macro, ProcessExitMacro, ReadInputMacro, WriteLnMacro, WriteMacro,
+    HasMacro, KeysMacro, LenMacro, Macro, ProcessExitMacro, PushMacro, ReadInputMacro,
+    RemoveMacro, UnwrapOrMacro, WriteLnMacro, WriteMacro,
 };
 use crate::backend::errors::compiler::compiler_errors::CompileError;
 use crate::backend::errors::compiler::compiler_errors::CompileError::UnknownMacro;
+use crate::backend::lexer::tokens::Span;
 use std::collections::HashMap;
 
 pub struct MacroManager {
@@ -10,9 +12,14 @@ pub struct MacroManager {
 }
 
 impl MacroManager {
-    pub fn get_macro_mut(&mut self, name: &str) -> Result<&mut Box<dyn Macro>, CompileError> {
+    pub fn get_macro_mut(
+        &mut self,
+        name: &str,
+        span: &Span,
+    ) -> Result<&mut Box<dyn Macro>, CompileError> {
         self.macros.get_mut(name).ok_or(UnknownMacro {
             name: name.to_string(),
+            span: span.clone(),
         })
     }
     pub fn new() -> Self {
@@ -23,6 +30,12 @@ impl MacroManager {
         register.register("write", WriteMacro);
         register.register("processExit", ProcessExitMacro);
         register.register("readInput", ReadInputMacro);
+        register.register("len", LenMacro);
+        register.register("push", PushMacro);
+        register.register("has", HasMacro);
+        register.register("remove", RemoveMacro);
+        register.register("keys", KeysMacro);
+        register.register("unwrapOr", UnwrapOrMacro);
         register
     }
     pub fn register<M: Macro + 'static>(&mut self, name: &str, mac: M) {
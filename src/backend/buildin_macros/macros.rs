@@ -1,9 +1,11 @@
-use crate::backend::compiler::instructions::Instructions::{ProcessExit, ReadInput, WriteLastOnStack};
+use crate::backend::compiler::assign_target::compile_index;
+use crate::backend::compiler::instructions::Instructions::{Equal, Jump, JumpIfFalse, Len, LoadVar, MapHas, MapKeys, MapRemove, ProcessExit, PushUndef, ReadInput, SaveVar, WriteLastOnStack};
+use crate::backend::lexer::tokens::Span;
 use crate::{
     backend::compiler::{
         byte_code::{Compilable, Compiler},
         comptime_variable_checker::comptime_value_for_check::ComptimeValueType::{
-            self, Array, Bool, StringValue, Void,Float
+            self, Array, Bool, Function, Map, Optional, StringValue, Struct, Enum, Tuple, TypeParam, Void,Float
         },
         instructions::Instructions::WriteLnLastOnStack,
     },
@@ -16,6 +18,7 @@ pub trait Macro {
         &self,
         out: &mut Compiler,
         args: &[Box<dyn Compilable>],
+        span: &Span,
     ) -> Result<ComptimeValueType, CompileError>;
 }
 
@@ -26,19 +29,21 @@ impl Macro for WriteLnMacro {
         &self,
         compiler: &mut Compiler,
         args: &[Box<dyn Compilable>],
+        _span: &Span,
     ) -> Result<ComptimeValueType, CompileError> {
         for arg in args {
             let value = arg.compile(compiler)?;
+            let span = arg.span().clone();
             match value {
-                StringValue | Int | Float => compiler.out.push(WriteLnLastOnStack),
-                Bool => {
-                    return Err(CompileError::ExpectedPrintable { found: Bool });
-                }
+                StringValue | Int | Float | Bool => compiler.out.push(WriteLnLastOnStack),
                 Void => {
-                    return Err(CompileError::ExpectedPrintable { found: Void });
+                    return Err(CompileError::ExpectedPrintable { found: Void, span });
+                }
+                found @ (Array(_) | Map(_, _) | Tuple(_) | Struct(_) | Enum(_) | Function(_, _) | TypeParam(_)) => {
+                    return Err(CompileError::ExpectedPrintable { found, span });
                 }
-                Array(t) => {
-                    return Err(CompileError::ExpectedPrintable { found: Array(t) });
+                found @ Optional(_) => {
+                    return Err(CompileError::UncheckedOptional { found, span });
                 }
             }
         }
@@ -53,19 +58,21 @@ impl Macro for WriteMacro {
         &self,
         compiler: &mut Compiler,
         args: &[Box<dyn Compilable>],
+        _span: &Span,
     ) -> Result<ComptimeValueType, CompileError> {
         for arg in args {
             let value = arg.compile(compiler)?;
+            let span = arg.span().clone();
             match value {
-                StringValue | Int | Float => compiler.out.push(WriteLastOnStack),
-                Bool => {
-                    return Err(CompileError::ExpectedPrintable { found: Bool });
-                }
+                StringValue | Int | Float | Bool => compiler.out.push(WriteLastOnStack),
                 Void => {
-                    return Err(CompileError::ExpectedPrintable { found: Void });
+                    return Err(CompileError::ExpectedPrintable { found: Void, span });
+                }
+                found @ (Array(_) | Map(_, _) | Tuple(_) | Struct(_) | Enum(_) | Function(_, _) | TypeParam(_)) => {
+                    return Err(CompileError::ExpectedPrintable { found, span });
                 }
-                Array(t) => {
-                    return Err(CompileError::ExpectedPrintable { found: Array(t) });
+                found @ Optional(_) => {
+                    return Err(CompileError::UncheckedOptional { found, span });
                 }
             }
         }
@@ -80,22 +87,25 @@ impl Macro for ProcessExitMacro {
         &self,
         out: &mut Compiler,
         args: &[Box<dyn Compilable>],
+        span: &Span,
     ) -> Result<ComptimeValueType, CompileError> {
         if args.len() != 1 {
-            return Err(CompileError::WrongMacroArgCount {
+            Err(CompileError::WrongMacroArgCount {
                 expected: 1,
                 found: args.len(),
-            });
+                span: span.clone(),
+            })
         } else {
             let value = args[0].compile(out)?;
             match value {
                 Int => {
                     out.out.push(ProcessExit);
-                    return Ok(Void);
+                    Ok(Void)
                 }
                 _ => Err(TypeMismatch {
                     expected: Int,
                     found: value,
+                    span: args[0].span().clone(),
                 }),
             }
         }
@@ -109,25 +119,247 @@ impl Macro for ReadInputMacro {
         &self,
         out: &mut Compiler,
         args: &[Box<dyn Compilable>],
+        span: &Span,
     ) -> Result<ComptimeValueType, CompileError> {
         if args.len() != 1 {
-            return Err(CompileError::WrongMacroArgCount {
+            Err(CompileError::WrongMacroArgCount {
                 expected: 1,
                 found: args.len(),
-            });
+                span: span.clone(),
+            })
         } else {
             let value = args[0].compile(out)?;
             match value {
                 StringValue => {
                     out.out.push(WriteLastOnStack);
                     out.out.push(ReadInput);
-                    return Ok(StringValue);
+                    Ok(StringValue)
                 }
                 _ => Err(TypeMismatch {
                     expected: StringValue,
                     found: value,
+                    span: args[0].span().clone(),
                 }),
             }
         }
     }
 }
+
+pub struct LenMacro;
+
+impl Macro for LenMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 1 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 1,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        match args[0].compile(out)? {
+            Array(_) | Map(_, _) | StringValue => {
+                out.out.push(Len);
+                Ok(Int)
+            }
+            found => Err(CompileError::NotIndexable {
+                found,
+                span: args[0].span().clone(),
+            }),
+        }
+    }
+}
+
+pub struct PushMacro;
+
+impl Macro for PushMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 2 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 2,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        // The array is changed in place, so it has to be something that can be assigned
+        let target = args[0].assign_target().ok_or_else(|| CompileError::NotAssignable {
+            span: args[0].span().clone(),
+        })?;
+        target.compile_push(out, |out, array_type| {
+            let value = args[1].compile(out)?;
+            let Array(element_type) = array_type else {
+                return Err(TypeMismatch {
+                    expected: Array(Box::new(value)),
+                    found: array_type,
+                    span: args[0].span().clone(),
+                });
+            };
+            if !element_type.accepts(&value) {
+                return Err(TypeMismatch {
+                    expected: *element_type,
+                    found: value,
+                    span: args[1].span().clone(),
+                });
+            }
+            Ok(())
+        })?;
+        Ok(Void)
+    }
+}
+
+/// Key and value types of a map passed to a macro.
+fn map_types(
+    found: ComptimeValueType,
+    span: &Span,
+) -> Result<(ComptimeValueType, ComptimeValueType), CompileError> {
+    match found {
+        Map(key, value) => Ok((*key, *value)),
+        found => Err(CompileError::ExpectedMap {
+            found,
+            span: span.clone(),
+        }),
+    }
+}
+
+pub struct HasMacro;
+
+impl Macro for HasMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 2 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 2,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        let found = args[0].compile(out)?;
+        let (key_type, _) = map_types(found, args[0].span())?;
+        compile_index(out, args[1].as_ref(), &key_type)?;
+        out.out.push(MapHas);
+        Ok(Bool)
+    }
+}
+
+pub struct RemoveMacro;
+
+impl Macro for RemoveMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 2 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 2,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        // Like `push!`, the map is changed in place
+        let target = args[0].assign_target().ok_or_else(|| CompileError::NotAssignable {
+            span: args[0].span().clone(),
+        })?;
+        target.compile_store(out, true, |out, map_type| {
+            let (key_type, _) = map_types(map_type, args[0].span())?;
+            compile_index(out, args[1].as_ref(), &key_type)?;
+            out.out.push(MapRemove);
+            Ok(())
+        })?;
+        Ok(Void)
+    }
+}
+
+pub struct KeysMacro;
+
+impl Macro for KeysMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 1 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 1,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        let found = args[0].compile(out)?;
+        let (key_type, _) = map_types(found, args[0].span())?;
+        out.out.push(MapKeys);
+        Ok(Array(Box::new(key_type)))
+    }
+}
+
+pub struct UnwrapOrMacro;
+
+impl Macro for UnwrapOrMacro {
+    fn compile(
+        &self,
+        out: &mut Compiler,
+        args: &[Box<dyn Compilable>],
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if args.len() != 2 {
+            return Err(CompileError::WrongMacroArgCount {
+                expected: 2,
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        let found = args[0].compile(out)?;
+        let Optional(value_type) = found else {
+            return Err(TypeMismatch {
+                expected: Optional(Box::new(found.clone())),
+                found,
+                span: args[0].span().clone(),
+            });
+        };
+        let temp = out.temp_variable("optional");
+        out.out.push(SaveVar(temp.clone()));
+        out.out.push(LoadVar(temp.clone()));
+        out.out.push(PushUndef);
+        out.out.push(Equal);
+        let jump_if_set = out.out.len();
+        out.out.push(JumpIfFalse(0));
+
+        let default = args[1].compile(out)?;
+        // A default that may be undef itself keeps the result optional
+        let result = match default {
+            Optional(_) => Optional(value_type.clone()),
+            _ => *value_type.clone(),
+        };
+        if !Optional(value_type).accepts(&default) {
+            return Err(TypeMismatch {
+                expected: result,
+                found: default,
+                span: args[1].span().clone(),
+            });
+        }
+        let jump_end = out.out.len();
+        out.out.push(Jump(0));
+
+        let set = out.out.len();
+        out.out.push(LoadVar(temp));
+        let end = out.out.len();
+        out.out[jump_if_set] = JumpIfFalse(set);
+        out.out[jump_end] = Jump(end);
+        Ok(result)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::backend::{
+    ast::statements::structs::find_field,
+    compiler::{
+        byte_code::{check_defined, Compilable, Compiler},
+        comptime_variable_checker::comptime_value_for_check::ComptimeValueType,
+        instructions::Instructions,
+    },
+    errors::compiler::compiler_errors::CompileError,
+    lexer::tokens::Span,
+};
+
+/// One step from a variable down to the place being assigned.
+#[derive(Clone, Debug)]
+pub enum PathStep {
+    Field(String),
+    Index(Box<dyn Compilable>),
+}
+
+/// Place a value can be stored in, like `p`, `p.x` or `grid[i][j]`.
+#[derive(Clone, Debug)]
+pub struct AssignTarget {
+    pub variable: String,
+    pub path: Vec<PathStep>,
+    pub span: Span,
+}
+
+/// A `PathStep` after type checking.
+enum ResolvedStep {
+    Field(usize),
+    /// Temporary variable holding the already computed index
+    Index(String),
+}
+
+impl ResolvedStep {
+    fn load(&self, compiler: &mut Compiler) {
+        match self {
+            ResolvedStep::Field(idx) => compiler.out.push(Instructions::LoadField(*idx)),
+            ResolvedStep::Index(temp) => {
+                compiler.out.push(Instructions::LoadVar(temp.clone()));
+                compiler.out.push(Instructions::LoadIndex);
+            }
+        }
+    }
+    fn store(&self, compiler: &mut Compiler) {
+        match self {
+            ResolvedStep::Field(idx) => compiler.out.push(Instructions::StoreField(*idx)),
+            ResolvedStep::Index(temp) => {
+                compiler.out.push(Instructions::LoadVar(temp.clone()));
+                compiler.out.push(Instructions::StoreIndex);
+            }
+        }
+    }
+}
+
+impl AssignTarget {
+    /// Stores a new value into the target.
+    ///
+    /// `value` gets the type of the target and has to push the new value. When
+    /// `load_current` is set the current value is pushed before it is called.
+    /// The containers on the path are loaded, updated from the inside out and
+    /// the variable is saved again, since values are copied on load.
+    pub fn compile_store(
+        &self,
+        compiler: &mut Compiler,
+        load_current: bool,
+        value: impl FnOnce(&mut Compiler, ComptimeValueType) -> Result<(), CompileError>,
+    ) -> Result<(), CompileError> {
+        let mut current_type = self.variable_type(compiler)?;
+
+        // Indexes are computed once, before anything is loaded
+        let mut steps = Vec::new();
+        for step in &self.path {
+            match step {
+                PathStep::Field(field) => {
+                    let (idx, field_type) = find_field(compiler, &current_type, field, &self.span)?;
+                    steps.push(ResolvedStep::Field(idx));
+                    current_type = field_type;
+                }
+                PathStep::Index(index) => {
+                    let (key_type, element_type) = index_type(&current_type, &self.span)?;
+                    compile_index(compiler, index.as_ref(), &key_type)?;
+                    let temp = compiler.temp_variable("index");
+                    compiler.out.push(Instructions::SaveVar(temp.clone()));
+                    steps.push(ResolvedStep::Index(temp));
+                    current_type = element_type;
+                }
+            }
+        }
+
+        // A single index is stored in place, so the rest of the array or map isn't copied
+        if let [ResolvedStep::Index(temp)] = steps.as_slice() {
+            if load_current {
+                compiler.out.push(Instructions::LoadVar(temp.clone()));
+                compiler.load_variable_index(&self.variable);
+            }
+            value(compiler, current_type)?;
+            compiler.out.push(Instructions::LoadVar(temp.clone()));
+            compiler.store_variable_index(&self.variable);
+            return Ok(());
+        }
+
+        // Every container on the path is pushed, the innermost one last
+        let loaded = if load_current { steps.len() + 1 } else { steps.len() };
+        for depth in 0..loaded {
+            compiler.load_variable(&self.variable);
+            for step in &steps[..depth] {
+                step.load(compiler);
+            }
+        }
+
+        value(compiler, current_type)?;
+
+        for step in steps.iter().rev() {
+            step.store(compiler);
+        }
+        compiler.save_variable(&self.variable);
+        Ok(())
+    }
+
+    /// Appends to the array in the target.
+    ///
+    /// `value` gets the type of the target and has to push the new element.
+    /// A bare variable is changed in place, longer paths are stored like `compile_store`.
+    pub fn compile_push(
+        &self,
+        compiler: &mut Compiler,
+        value: impl FnOnce(&mut Compiler, ComptimeValueType) -> Result<(), CompileError>,
+    ) -> Result<(), CompileError> {
+        if !self.path.is_empty() {
+            return self.compile_store(compiler, true, |compiler, array_type| {
+                value(compiler, array_type)?;
+                compiler.out.push(Instructions::ArrayPush);
+                Ok(())
+            });
+        }
+        let array_type = self.variable_type(compiler)?;
+        value(compiler, array_type)?;
+        compiler.push_to_variable(&self.variable);
+        Ok(())
+    }
+
+    /// Type of the variable the target starts at, which has to be assignable.
+    fn variable_type(&self, compiler: &Compiler) -> Result<ComptimeValueType, CompileError> {
+        let var = compiler.context.get_variable(&self.variable).ok_or(
+            CompileError::UndefinedVariable {
+                name: self.variable.clone(),
+                span: self.span.clone(),
+            },
+        )?;
+        if var.is_const {
+            return Err(CompileError::reassignment(&self.variable, &self.span));
+        }
+        Ok(var.value_type.clone())
+    }
+}
+
+/// Index and element type of an indexed value of type `value_type`.
+pub fn index_type(
+    value_type: &ComptimeValueType,
+    span: &Span,
+) -> Result<(ComptimeValueType, ComptimeValueType), CompileError> {
+    check_defined(value_type, span)?;
+    match value_type {
+        ComptimeValueType::Array(element) => Ok((ComptimeValueType::Int, *element.clone())),
+        ComptimeValueType::Map(key, value) => Ok((*key.clone(), *value.clone())),
+        found => Err(CompileError::NotIndexable {
+            found: found.clone(),
+            span: span.clone(),
+        }),
+    }
+}
+
+/// Compiles an index expression, a number for arrays or a key for maps.
+pub fn compile_index(
+    compiler: &mut Compiler,
+    index: &dyn Compilable,
+    expected: &ComptimeValueType,
+) -> Result<(), CompileError> {
+    let found = index.compile(compiler)?;
+    if found != *expected {
+        return Err(CompileError::TypeMismatch {
+            expected: expected.clone(),
+            found,
+            span: index.span().clone(),
+        });
+    }
+    Ok(())
+}
@@ -1,24 +1,29 @@
 use crate::backend::{
-    ast::nodes::{
-        ArrayNode, BinaryOpNode, BoolNode, CallType, FloatNode, FunctionCallNode, NumberNode, PrefixExpressionNode, ProgramNode, StringNode, VariableAccessNode, VariableAssignNode, VariableDefineNode
-    },
+    ast::{nodes::{
+        ArrayNode, BinaryOpNode, BindingPattern, IndexNode, MapNode, TupleNode, PathAssignNode, BoolNode, CallType, ExpressionStatementNode, FloatNode, FunctionCallNode, InterpolatedStringNode, InterpolationPart, NumberNode, PrefixExpressionNode, ProgramNode, StringNode, UndefNode, VariableAccessNode, VariableAssignNode, VariableDefineNode
+    }, statements::functions::{args_node::FunctionArgs, closures::compile_value_call, function_nodes::FunctionDefineNode}},
     buildin_macros::get_macro::MacroManager,
     compiler::{
+         assign_target::{compile_index, index_type, AssignTarget, PathStep},
          comptime_variable_checker::{
             comptime_context::{CompileContext, ComptimeVariable},
             comptime_value_for_check::ComptimeValueType::{
-                self, Array, Bool, Float, Int, StringValue, Void,
+                self, Array, Bool, Float, Function, Int, Map, Optional, StringValue, Tuple, Void,
             },
-        }, functions_compiler_context::CompileTimeFunctionForCheck, instructions::Instructions::{
+        }, functions_compiler_context::CompileTimeFunctionForCheck, loop_context::LoopContext, instructions::Instructions::{
             self, Add, Div, Halt, LoadVar, Mul, PushBool, PushNumber, PushString, Sub,
         }, optimization::optimze::optimize
     },
     errors::compiler::compiler_errors::CompileError::{
         self, CannotInferType, TypeMismatch,
     },
-    lexer::tokens::TokenKind::{self, TRUE},
+    lexer::tokens::{
+        Span,
+        TokenKind::{self, TRUE},
+    },
 };
 use CompileError::ConstantWithoutValue;
+use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
 
 pub trait CompilableClone {
@@ -36,10 +41,72 @@ where
 pub trait Compilable: Debug + CompilableClone {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError>;
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result;
+    /// Source location of the node, used to point diagnostics at it.
+    fn span(&self) -> &Span;
+    /// Whether running this statement always ends in a `return`.
+    fn always_returns(&self) -> bool {
+        false
+    }
+    /// Whether a `break` in this statement can leave the loop labeled `label` around it,
+    /// `innermost` is set while there is no other loop in between.
+    fn breaks_out(&self, _label: Option<&str>, _innermost: bool) -> bool {
+        false
+    }
+    /// Registers what this node declares before any code is compiled,
+    /// so it can be used above its definition.
+    fn declare(&self, _compiler: &mut Compiler) -> Result<(), CompileError> {
+        Ok(())
+    }
+    /// Place this expression refers to when it is assigned to, `None` for values.
+    fn assign_target(&self) -> Option<AssignTarget> {
+        None
+    }
+    /// Registers the types this node defines, runs before `declare`
+    /// so signatures can use types defined further down.
+    fn declare_types(&self, _compiler: &mut Compiler) -> Result<(), CompileError> {
+        Ok(())
+    }
+    /// Whether this is the `undef` literal.
+    fn is_undef(&self) -> bool {
+        false
+    }
+    /// Expressions and statements directly inside this node.
+    fn children(&self) -> Vec<&dyn Compilable> {
+        Vec::new()
+    }
+    /// Whether this node itself calls a function or defines code that runs later,
+    /// either of which can change top level variables behind the surrounding code.
+    fn calls_function(&self) -> bool {
+        false
+    }
+    /// Variables known not to be `undef` when this condition evaluates to `outcome`,
+    /// like `x` for `x != undef` being true.
+    fn defined_when(&self, _outcome: bool) -> Vec<String> {
+        Vec::new()
+    }
 }
 pub fn indent_fn(n: usize) -> String {
     "  ".repeat(n)
 }
+/// Statements of a block as references.
+pub fn nodes(body: &[Box<dyn Compilable>]) -> Vec<&dyn Compilable> {
+    body.iter().map(|stmt| stmt.as_ref()).collect()
+}
+/// Whether running `node` can call a function, looking at everything inside of it.
+pub fn may_call(node: &dyn Compilable) -> bool {
+    node.calls_function() || node.children().into_iter().any(may_call)
+}
+
+/// Fails for optionals, which have to be checked before their value is used.
+pub fn check_defined(value_type: &ComptimeValueType, span: &Span) -> Result<(), CompileError> {
+    match value_type {
+        Optional(_) => Err(CompileError::UncheckedOptional {
+            found: value_type.clone(),
+            span: span.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
 
 impl Clone for Box<dyn Compilable>  {
     fn clone(&self) -> Self {
@@ -51,6 +118,21 @@ pub struct Compiler {
     pub context: CompileContext,
     pub out: Vec<Instructions>,
     pub macros: MacroManager,
+    pub loops: Vec<LoopContext>,
+    /// Number of `try` blocks around the code being compiled
+    pub try_depth: usize,
+    /// Declared return types of the functions being compiled, innermost last
+    pub return_types: Vec<ComptimeValueType>,
+    /// Index of the first instruction of every compiled function body
+    pub function_addresses: HashMap<String, usize>,
+    /// `Call` instructions waiting for the address of their function
+    pending_calls: Vec<(usize, String)>,
+    /// Definitions of generic functions, their bodies are compiled per instance
+    pub generic_functions: HashMap<String, FunctionDefineNode>,
+    /// Instances that are called but not compiled yet, as the generic function,
+    /// the address name of the instance and the types of the type parameters
+    pending_instances: Vec<(String, String, HashMap<String, ComptimeValueType>)>,
+    temp_counter: usize,
 }
 
 
@@ -65,6 +147,230 @@ impl Compiler {
             context: CompileContext::new(),
             out: Vec::new(),
             macros: MacroManager::new(),
+            loops: Vec::new(),
+            try_depth: 0,
+            return_types: Vec::new(),
+            function_addresses: HashMap::new(),
+            pending_calls: Vec::new(),
+            generic_functions: HashMap::new(),
+            pending_instances: Vec::new(),
+            temp_counter: 0,
+        }
+    }
+    /// Type checks an expression without keeping the code it compiles to.
+    pub fn check_type(&mut self, expr: &dyn Compilable) -> Result<ComptimeValueType, CompileError> {
+        let out_len = self.out.len();
+        let pending_len = self.pending_calls.len();
+        let result = expr.compile(self);
+        self.out.truncate(out_len);
+        self.pending_calls.truncate(pending_len);
+        result
+    }
+    /// Gives the optional variables in `names` the type of their value while `guarded` is compiled,
+    /// until `restore` is called.
+    ///
+    /// Functions can set top level variables back to `undef`, so those stay optional
+    /// when `guarded` calls a function or defines one.
+    pub fn narrow(
+        &mut self,
+        names: Vec<String>,
+        guarded: &[&dyn Compilable],
+    ) -> Vec<(String, ComptimeValueType)> {
+        let calls = guarded.iter().any(|node| may_call(*node));
+        let mut narrowed = Vec::new();
+        for name in names {
+            let Some(Optional(inner)) = self.context.get_variable(&name).map(|v| v.value_type.clone()) else {
+                continue;
+            };
+            if calls && self.context.is_top_level(&name) {
+                continue;
+            }
+            if let Some(old) = self.context.retype_variable(&name, *inner) {
+                narrowed.push((name, old));
+            }
+        }
+        narrowed
+    }
+    /// Undoes `narrow`, once the code the check applies to is compiled.
+    pub fn restore(&mut self, narrowed: Vec<(String, ComptimeValueType)>) {
+        for (name, old) in narrowed.into_iter().rev() {
+            self.context.retype_variable(&name, old);
+        }
+    }
+    /// Pushes the value a variable declared without one starts with.
+    ///
+    /// Only optionals, arrays and maps have one, `undef` or empty, so every
+    /// other variable has to be given a value.
+    pub fn push_default(
+        &mut self,
+        value_type: &ComptimeValueType,
+        span: &Span,
+    ) -> Result<(), CompileError> {
+        match value_type {
+            Array(_) => self.out.push(Instructions::MakeArray(0)),
+            Map(_, _) => self.out.push(Instructions::MakeMap(0)),
+            Optional(_) => self.out.push(Instructions::PushUndef),
+            _ => {
+                return Err(CompileError::NoDefaultValue {
+                    found: value_type.clone(),
+                    span: span.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Emits a call whose address is filled in once every function is compiled.
+    pub fn call_function(&mut self, name: &str, argc: usize) {
+        self.pending_calls.push((self.out.len(), name.to_string()));
+        self.out.push(Instructions::Call(0, argc));
+    }
+    /// Makes sure the instance of a generic function gets compiled.
+    pub fn instantiate(
+        &mut self,
+        generic: &str,
+        instance: String,
+        bindings: HashMap<String, ComptimeValueType>,
+    ) {
+        let known = self.function_addresses.contains_key(&instance)
+            || self.pending_instances.iter().any(|(_, name, _)| *name == instance);
+        if !known {
+            self.pending_instances.push((generic.to_string(), instance, bindings));
+        }
+    }
+    /// Compiles the instances of generic functions, including the ones they call themselves.
+    fn compile_instances(&mut self) -> Result<(), CompileError> {
+        while let Some((generic, instance, bindings)) = self.pending_instances.pop() {
+            let function = self.generic_functions[&generic].clone();
+            function.compile_instance(self, instance, bindings)?;
+        }
+        Ok(())
+    }
+    /// Pushes a named function as a value, it captures nothing.
+    pub fn function_value(
+        &mut self,
+        name: &str,
+        span: &Span,
+    ) -> Result<ComptimeValueType, CompileError> {
+        let function = self.context.get_fn(name, span)?;
+        // There are no arguments to infer the types from
+        if let Some(param) = function.type_params.first() {
+            return Err(CompileError::CannotInferTypeParameter {
+                name: param.name.clone(),
+                function: name.to_string(),
+                span: span.clone(),
+            });
+        }
+        let params = function
+            .args
+            .iter()
+            .map(|arg| self.context.get_type(&arg.argument_type, &arg.span))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.pending_calls.push((self.out.len(), name.to_string()));
+        self.out.push(Instructions::MakeClosure(0, Vec::new()));
+        Ok(Function(params, Box::new(function.return_type)))
+    }
+    fn patch_calls(&mut self) {
+        for (pos, name) in std::mem::take(&mut self.pending_calls) {
+            let Some(&addr) = self.function_addresses.get(&name) else {
+                continue;
+            };
+            match &mut self.out[pos] {
+                Instructions::Call(address, _) | Instructions::MakeClosure(address, _) => {
+                    *address = addr;
+                }
+                _ => {}
+            }
+        }
+    }
+    /// Pushes the value of a variable, reading top level variables from inside functions too.
+    pub fn load_variable(&mut self, name: &str) {
+        let storage = self.context.storage_name(name);
+        if self.context.is_global(name) {
+            self.out.push(Instructions::LoadGlobal(storage));
+        } else {
+            self.out.push(LoadVar(storage));
+        }
+    }
+    /// Stores the top of the stack into an existing variable.
+    pub fn save_variable(&mut self, name: &str) {
+        let storage = self.context.storage_name(name);
+        if self.context.is_global(name) {
+            self.out.push(Instructions::SaveGlobal(storage));
+        } else {
+            self.out.push(Instructions::SaveVar(storage));
+        }
+    }
+    /// Loads one element of an array or map variable, the index is on top of the stack.
+    pub fn load_variable_index(&mut self, name: &str) {
+        let storage = self.context.storage_name(name);
+        if self.context.is_global(name) {
+            self.out.push(Instructions::LoadGlobalIndex(storage));
+        } else {
+            self.out.push(Instructions::LoadVarIndex(storage));
+        }
+    }
+    /// Stores the value below the index on top of the stack into an element of a variable.
+    pub fn store_variable_index(&mut self, name: &str) {
+        let storage = self.context.storage_name(name);
+        if self.context.is_global(name) {
+            self.out.push(Instructions::StoreGlobalIndex(storage));
+        } else {
+            self.out.push(Instructions::StoreVarIndex(storage));
+        }
+    }
+    /// Appends the top of the stack to an array variable.
+    pub fn push_to_variable(&mut self, name: &str) {
+        let storage = self.context.storage_name(name);
+        if self.context.is_global(name) {
+            self.out.push(Instructions::PushGlobal(storage));
+        } else {
+            self.out.push(Instructions::PushVar(storage));
+        }
+    }
+    /// Returns a fresh variable name that can't clash with user variables.
+    pub fn temp_variable(&mut self, name: &str) -> String {
+        self.temp_counter += 1;
+        format!("{}#{}", name, self.temp_counter)
+    }
+    pub fn enter_loop(&mut self, label: Option<String>) {
+        self.loops.push(LoopContext::new(label, self.try_depth));
+    }
+    /// Ends the `try` blocks opened since there were `depth` of them, before jumping out of them.
+    pub fn leave_tries(&mut self, depth: usize) {
+        for _ in depth..self.try_depth {
+            self.out.push(Instructions::PopHandler);
+        }
+    }
+    /// Closes the innermost loop and patches its pending `break`/`continue` jumps.
+    pub fn exit_loop(&mut self, continue_target: usize, break_target: usize) {
+        if let Some(loop_context) = self.loops.pop() {
+            loop_context.patch(&mut self.out, continue_target, break_target);
+        }
+    }
+    /// Finds the loop a `break`/`continue` refers to, the innermost one when there is no label.
+    pub fn get_loop(
+        &mut self,
+        label: &Option<String>,
+        keyword: &'static str,
+        span: &Span,
+    ) -> Result<&mut LoopContext, CompileError> {
+        if self.loops.is_empty() {
+            return Err(CompileError::OutsideOfLoop {
+                keyword,
+                span: span.clone(),
+            });
+        }
+        match label {
+            None => Ok(self.loops.last_mut().unwrap()),
+            Some(label) => self
+                .loops
+                .iter_mut()
+                .rev()
+                .find(|l| l.label.as_ref() == Some(label))
+                .ok_or_else(|| CompileError::UndefinedLabel {
+                    label: label.clone(),
+                    span: span.clone(),
+                }),
         }
     }
     pub fn optimize(&mut self) {
@@ -80,6 +386,9 @@ impl Compilable for NumberNode {
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}Number({})", indent_fn(indent), self.number)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
 }
 
 impl Compilable for FloatNode {
@@ -90,16 +399,65 @@ impl Compilable for FloatNode {
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}Float({})", indent_fn(indent), self.number)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
 }
 
 impl Compilable for PrefixExpressionNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        todo!()
+        let value = self.value.compile(compiler)?;
+        check_defined(&value, self.value.span())?;
+        match self.prefix {
+            TokenKind::NOT => match value {
+                Bool => {
+                    compiler.out.push(Instructions::Not);
+                    Ok(Bool)
+                }
+                found => Err(TypeMismatch {
+                    expected: Bool,
+                    found,
+                    span: self.value.span().clone(),
+                }),
+            },
+            TokenKind::MINUS => match value {
+                Int | Float => {
+                    compiler.out.push(Instructions::Negate);
+                    Ok(value)
+                }
+                found => Err(TypeMismatch {
+                    expected: Int,
+                    found,
+                    span: self.value.span().clone(),
+                }),
+            },
+            TokenKind::PLUS => match value {
+                Int | Float => Ok(value),
+                found => Err(TypeMismatch {
+                    expected: Int,
+                    found,
+                    span: self.value.span().clone(),
+                }),
+            },
+            _ => unreachable!(),
+        }
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         write!(f,"{}{:?}",indent_fn(indent+1),self.prefix)?;
         self.value.fmt_with_indent(f, 0)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.value.as_ref()]
+    }
+    fn defined_when(&self, outcome: bool) -> Vec<String> {
+        match self.prefix {
+            TokenKind::NOT => self.value.defined_when(!outcome),
+            _ => Vec::new(),
+        }
+    }
 }
 impl Debug for PrefixExpressionNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -107,10 +465,106 @@ impl Debug for PrefixExpressionNode {
     }
 }
 
+impl BinaryOpNode {
+    /// Compiles `&&` / `||` so the right operand only runs when it decides the result.
+    fn compile_logical(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let (op, short_circuit_value) = match self.op_tok {
+            TokenKind::AND => ("&&", false),
+            _ => ("||", true),
+        };
+        let left = self.left.compile(compiler)?;
+        let short_circuit_jump = compiler.out.len();
+        compiler.out.push(if short_circuit_value {
+            Instructions::JumpIfTrue(0)
+        } else {
+            Instructions::JumpIfFalse(0)
+        });
+        // In `x != undef && x > 0` the right side only runs when `x` is set
+        let narrowed = compiler.narrow(self.left.defined_when(!short_circuit_value), &[self.right.as_ref()]);
+        let right = self.right.compile(compiler);
+        compiler.restore(narrowed);
+        let right = right?;
+        if left != Bool || right != Bool {
+            return Err(CompileError::InvalidBinaryOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+                span: self.span.clone(),
+            });
+        }
+        let end_jump = compiler.out.len();
+        compiler.out.push(Instructions::Jump(0));
+
+        let short_circuit_target = compiler.out.len();
+        compiler.out.push(PushBool(short_circuit_value));
+        let end_target = compiler.out.len();
+
+        compiler.out[short_circuit_jump] = if short_circuit_value {
+            Instructions::JumpIfTrue(short_circuit_target)
+        } else {
+            Instructions::JumpIfFalse(short_circuit_target)
+        };
+        compiler.out[end_jump] = Instructions::Jump(end_target);
+        Ok(Bool)
+    }
+    /// Optionals can only be compared, to `undef` or to values of their type.
+    fn compile_optional(
+        &self,
+        compiler: &mut Compiler,
+        left: ComptimeValueType,
+        right: ComptimeValueType,
+    ) -> Result<ComptimeValueType, CompileError> {
+        let (op, instruction) = match self.op_tok {
+            TokenKind::EQUALEQUAL => ("==", Instructions::Equal),
+            TokenKind::NOTEQUAL => ("!=", Instructions::NotEqual),
+            _ => {
+                let (found, side) = match left {
+                    Optional(_) => (left, &self.left),
+                    _ => (right, &self.right),
+                };
+                return Err(CompileError::UncheckedOptional {
+                    found,
+                    span: side.span().clone(),
+                });
+            }
+        };
+        if !left.accepts(&right) && !right.accepts(&left) {
+            return Err(CompileError::InvalidBinaryOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+                span: self.span.clone(),
+            });
+        }
+        compiler.out.push(instruction);
+        Ok(Bool)
+    }
+    /// The variable compared in `x == undef`, when the other side is `undef`.
+    fn undef_compared(&self) -> Option<String> {
+        let compared = if self.right.is_undef() {
+            &self.left
+        } else if self.left.is_undef() {
+            &self.right
+        } else {
+            return None;
+        };
+        compared
+            .assign_target()
+            .filter(|target| target.path.is_empty())
+            .map(|target| target.variable)
+    }
+}
+
 impl Compilable for BinaryOpNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        let right = self.left.compile(compiler)?;
-        let left = self.right.compile(compiler)?;
+        if matches!(self.op_tok, TokenKind::AND | TokenKind::OR) {
+            return self.compile_logical(compiler);
+        }
+        let left = self.left.compile(compiler)?;
+        let right = self.right.compile(compiler)?;
+        if matches!(left, Optional(_)) || matches!(right, Optional(_)) {
+            return self.compile_optional(compiler, left, right);
+        }
         match self.op_tok {
             TokenKind::PLUS => match (&left, &right) {
                 (Int, Int) => {
@@ -125,7 +579,12 @@ impl Compilable for BinaryOpNode {
                     compiler.out.push(Add);
                     Ok(StringValue)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "+", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "+",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::MINUS => match (&left, &right) {
                 (Int, Int) => {
@@ -136,7 +595,12 @@ impl Compilable for BinaryOpNode {
                     compiler.out.push(Sub);
                     Ok(Float)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "-", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "-",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::TIMES => match (&left, &right) {
                 (Int, Int) => {
@@ -147,7 +611,12 @@ impl Compilable for BinaryOpNode {
                     compiler.out.push(Mul);
                     Ok(Float)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "*", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "*",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::DIVIDE => match (&left, &right) {
                 (Int, Int) => {
@@ -158,28 +627,104 @@ impl Compilable for BinaryOpNode {
                     compiler.out.push(Div);
                     Ok(Float)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "/", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "/",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::MODULO => match (&left, &right) {
                 (Int, Int) => {
                     compiler.out.push(Instructions::Modulo);
                     Ok(Int)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "%", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "%",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::GREATER => match (&left, &right) {
                 (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
                     compiler.out.push(Instructions::GreaterThan);
                     Ok(Bool)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: ">", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: ">",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             TokenKind::LESS => match (&left, &right) {
                 (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
                     compiler.out.push(Instructions::LessThan);
                     Ok(Bool)
                 }
-                _ => Err(CompileError::InvalidBinaryOp { op: "<", left, right }),
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "<",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
+            },
+            TokenKind::GREATEREQUAL => match (&left, &right) {
+                (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
+                    compiler.out.push(Instructions::GreaterEqual);
+                    Ok(Bool)
+                }
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: ">=",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
+            },
+            TokenKind::LESSEQUAL => match (&left, &right) {
+                (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
+                    compiler.out.push(Instructions::LessEqual);
+                    Ok(Bool)
+                }
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "<=",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
+            },
+            TokenKind::EQUALEQUAL => match (&left, &right) {
+                (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
+                    compiler.out.push(Instructions::Equal);
+                    Ok(Bool)
+                }
+                (StringValue, StringValue) | (Bool, Bool) => {
+                    compiler.out.push(Instructions::Equal);
+                    Ok(Bool)
+                }
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "==",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
+            },
+            TokenKind::NOTEQUAL => match (&left, &right) {
+                (Int, Int) | (Float, Float) | (Int, Float) | (Float, Int) => {
+                    compiler.out.push(Instructions::NotEqual);
+                    Ok(Bool)
+                }
+                (StringValue, StringValue) | (Bool, Bool) => {
+                    compiler.out.push(Instructions::NotEqual);
+                    Ok(Bool)
+                }
+                _ => Err(CompileError::InvalidBinaryOp {
+                    op: "!=",
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: self.span.clone(),
+                }),
             },
             _ => unreachable!(),
         }
@@ -191,14 +736,63 @@ impl Compilable for BinaryOpNode {
         self.right.fmt_with_indent(f, indent + 2)?;
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+    fn defined_when(&self, outcome: bool) -> Vec<String> {
+        match (&self.op_tok, outcome) {
+            (TokenKind::NOTEQUAL, true) | (TokenKind::EQUALEQUAL, false) => {
+                self.undef_compared().into_iter().collect()
+            }
+            // Both sides of `a && b` are true when it is, both sides of `a || b` false when it is
+            (TokenKind::AND, true) | (TokenKind::OR, false) => {
+                let mut names = self.left.defined_when(outcome);
+                names.extend(self.right.defined_when(outcome));
+                names
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Compilable for ExpressionStatementNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        // The value of an expression used as a statement is thrown away
+        if self.expr.compile(compiler)? != Void {
+            compiler.out.push(Instructions::Pop);
+        }
+        Ok(Void)
+    }
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        self.expr.fmt_with_indent(f, indent)
+    }
+    fn span(&self) -> &Span {
+        self.expr.span()
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.expr.as_ref()]
+    }
 }
 
 impl Compilable for ProgramNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        for program_node in &self.program_nodes {
+            program_node.declare_types(compiler)?;
+        }
+        // Signatures first, so functions can be called before their definition
+        for program_node in &self.program_nodes {
+            program_node.declare(compiler)?;
+        }
         for program_node in &self.program_nodes {
             program_node.compile(compiler)?;
         }
+        // Before `Halt`, so the jump over the last instance lands on it
+        compiler.compile_instances()?;
         compiler.out.push(Halt);
+        compiler.patch_calls();
         Ok(Void)
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
@@ -208,21 +802,43 @@ impl Compilable for ProgramNode {
         }
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        nodes(&self.program_nodes)
+    }
 }
 
 impl Compilable for VariableAccessNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        let var = compiler.context.get_variable(&self.variable_name).ok_or(
-            CompileError::UndefinedVariable {
+        let Some(var) = compiler.context.get_variable(&self.variable_name) else {
+            // A function name without a call is the function itself
+            if compiler.context.get_fn(&self.variable_name, &self.span).is_ok() {
+                return compiler.function_value(&self.variable_name, &self.span);
+            }
+            return Err(CompileError::UndefinedVariable {
                 name: self.variable_name.clone(),
-            },
-        )?;
-        compiler.out.push(LoadVar(self.variable_name.clone()));
-        Ok(var.value_type.clone())
+                span: self.span.clone(),
+            });
+        };
+        let value_type = var.value_type.clone();
+        compiler.load_variable(&self.variable_name);
+        Ok(value_type)
+    }
+    fn assign_target(&self) -> Option<AssignTarget> {
+        Some(AssignTarget {
+            variable: self.variable_name.clone(),
+            path: Vec::new(),
+            span: self.span.clone(),
+        })
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}Var({})", indent_fn(indent), self.variable_name)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
 }
 
 impl Compilable for StringNode {
@@ -233,13 +849,121 @@ impl Compilable for StringNode {
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}String({})", indent_fn(indent), self.value)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+impl Compilable for InterpolatedStringNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let mut parts = self.parts.iter();
+        if let Some(InterpolationPart::Text(first)) = parts.next() {
+            compiler.out.push(PushString(first.clone()));
+        }
+        for part in parts {
+            match part {
+                InterpolationPart::Text(text) if text.is_empty() => continue,
+                InterpolationPart::Text(text) => compiler.out.push(PushString(text.clone())),
+                InterpolationPart::Expr(expr) => match expr.compile(compiler)? {
+                    StringValue => {}
+                    Int | Float | Bool => compiler.out.push(Instructions::ToString),
+                    found @ Optional(_) => {
+                        return Err(CompileError::UncheckedOptional {
+                            found,
+                            span: expr.span().clone(),
+                        });
+                    }
+                    found => {
+                        return Err(CompileError::ExpectedPrintable {
+                            found,
+                            span: expr.span().clone(),
+                        });
+                    }
+                },
+            }
+            compiler.out.push(Add);
+        }
+        Ok(StringValue)
+    }
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}InterpolatedString", indent_fn(indent))?;
+        for part in &self.parts {
+            match part {
+                InterpolationPart::Text(text) => {
+                    writeln!(f, "{}String({})", indent_fn(indent + 1), text)?
+                }
+                InterpolationPart::Expr(expr) => expr.fmt_with_indent(f, indent + 1)?,
+            }
+        }
+        Ok(())
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                InterpolationPart::Expr(expr) => Some(expr.as_ref()),
+                InterpolationPart::Text(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl BindingPattern {
+    /// Declares the variables of the pattern and stores the value on top of the stack in them.
+    fn bind(
+        &self,
+        compiler: &mut Compiler,
+        value_type: ComptimeValueType,
+        is_const: bool,
+        span: &Span,
+    ) -> Result<(), CompileError> {
+        match self {
+            BindingPattern::Name(name) if name == "_" => compiler.out.push(Instructions::Pop),
+            BindingPattern::Name(name) => {
+                compiler.context.add_variable(
+                    name.clone(),
+                    ComptimeVariable {
+                        value_type,
+                        is_const,
+                        storage: None,
+                    },
+                    span,
+                )?;
+                compiler.out.push(Instructions::SaveVar(name.clone()));
+            }
+            BindingPattern::Tuple(patterns) => {
+                let element_types = match value_type {
+                    Tuple(element_types) if element_types.len() == patterns.len() => element_types,
+                    found => {
+                        return Err(CompileError::PatternMismatch {
+                            pattern: self.to_string(),
+                            found,
+                            span: span.clone(),
+                        });
+                    }
+                };
+                let temp = compiler.temp_variable("tuple");
+                compiler.out.push(Instructions::SaveVar(temp.clone()));
+                for (idx, (pattern, element_type)) in patterns.iter().zip(element_types).enumerate() {
+                    compiler.out.push(LoadVar(temp.clone()));
+                    compiler.out.push(Instructions::LoadField(idx));
+                    pattern.bind(compiler, element_type, is_const, span)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Compilable for VariableDefineNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
         if self.is_const && self.value.is_none() {
             return Err(ConstantWithoutValue {
-                name: self.var_name.clone(),
+                name: self.pattern.to_string(),
+                span: self.span.clone(),
             });
         }
         /*
@@ -251,51 +975,54 @@ impl Compilable for VariableDefineNode {
             None
         };
         let declared_type = if let Some(t) = &self.value_type {
-            Some(CompileContext::get_type(t)?)
+            Some(compiler.context.get_type(t, &self.span)?)
         } else {
             None
         };
 
         let final_type = match (declared_type, inferred_type) {
-            (Some(d), Some(i)) if d == i => d,
+            (Some(d), Some(i)) if d.accepts(&i) => d,
             (Some(d), Some(i)) => {
                 return Err(TypeMismatch {
                     expected: d,
                     found: i,
+                    span: self.value.as_ref().map_or(&self.span, |v| v.span()).clone(),
                 });
             }
             (Some(d), None) => {
-                match d {
-                    StringValue => compiler.out.push(PushString("".to_string())),
-                    Int => compiler.out.push(PushNumber(0f32)),
-                    Float => compiler.out.push(PushNumber(0f32)),
-                    Bool => compiler.out.push(PushBool(false)),
-                    Array(t) => {
-                        todo!()
-                    }
-                    Void => {
-                        unreachable!()
-                    }
-                }
+                compiler.push_default(&d, &self.span)?;
                 d
             }
-            (None, Some(i)) => i,
-            (None, None) => {
+            // `[]` and `{}` alone don't tell the element types
+            (None, Some(i))
+                if i != Void
+                    && i != Array(Box::new(Void))
+                    && i != Map(Box::new(Void), Box::new(Void))
+                    && i != Optional(Box::new(Void)) =>
+            {
+                i
+            }
+            (None, _) => {
                 return Err(CannotInferType {
-                    name: self.var_name.clone(),
+                    name: self.pattern.to_string(),
+                    span: self.span.clone(),
                 });
             }
         };
 
-        let var_name = self.var_name.clone();
-        compiler.context.add_variable(var_name, ComptimeVariable { value_type: final_type, is_const: self.is_const })?;
-        compiler
-            .out
-            .push(Instructions::SaveVar(self.var_name.clone()));
+        self.pattern
+            .bind(compiler, final_type, self.is_const, &self.span)?;
         Ok(Void)
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
-        write!(f, "{}var:{:?}=", indent_fn(indent), self.value_type)?;
+        write!(
+            f,
+            "{}var {}:{:?} (doc: {:?})=",
+            indent_fn(indent),
+            self.pattern,
+            self.value_type,
+            self.doc
+        )?;
         if let Some(value) = &self.value {
             value.fmt_with_indent(f, 0)?;
         } else {
@@ -303,18 +1030,43 @@ impl Compilable for VariableDefineNode {
         }
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        self.value.iter().map(|value| value.as_ref()).collect()
+    }
 }
 
 impl Compilable for BoolNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
         compiler
             .out
-            .push(PushBool(if self.value == TRUE { true } else { false }));
+            .push(PushBool(self.value == TRUE));
         Ok(Bool)
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}String({:?})", indent_fn(indent), self.value)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+impl Compilable for UndefNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        compiler.out.push(Instructions::PushUndef);
+        Ok(Optional(Box::new(Void)))
+    }
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}Undef", indent_fn(indent))
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn is_undef(&self) -> bool {
+        true
+    }
 }
 
 impl Compilable for VariableAssignNode {
@@ -323,40 +1075,63 @@ impl Compilable for VariableAssignNode {
             let var = compiler.context.get_variable(&self.name).ok_or(
                 CompileError::UndefinedVariable {
                     name: self.name.clone(),
+                    span: self.span.clone(),
                 },
             )?;
             (var.is_const, var.value_type.clone())
         };
         if is_const {
-            return Err(CompileError::ConstReassignment {
-                name: self.name.clone(),
-            });
+            return Err(CompileError::reassignment(&self.name, &self.span));
         }
 
         let value_type = self.value.compile(compiler)?;
 
-        if value_type != expected_type {
+        if !expected_type.accepts(&value_type) {
             return Err(TypeMismatch {
                 expected: expected_type,
                 found: value_type,
+                span: self.value.span().clone(),
             });
         }
 
-        compiler.out.push(Instructions::SaveVar(self.name.clone()));
-        Ok(value_type)
+        compiler.save_variable(&self.name);
+        Ok(Void)
     }
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
         writeln!(f, "{}{}=", indent_fn(indent), self.name)?;
         self.value.fmt(f)?;
         Ok(())
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.value.as_ref()]
+    }
 }
 /*
  * Array node
  */
 impl Compilable for ArrayNode {
-    fn compile(&self, _compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
-        todo!()
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        // `[]` gets its element type from where it is used
+        let mut element_type = Void;
+        for element in &self.elements {
+            let found = element.compile(compiler)?;
+            if element_type == Void {
+                element_type = found;
+            } else if let Some(unified) = element_type.unify(&found) {
+                element_type = unified;
+            } else {
+                return Err(TypeMismatch {
+                    expected: element_type,
+                    found,
+                    span: element.span().clone(),
+                });
+            }
+        }
+        compiler.out.push(Instructions::MakeArray(self.elements.len()));
+        Ok(Array(Box::new(element_type)))
     }
 
     fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
@@ -366,46 +1141,398 @@ impl Compilable for ArrayNode {
         }
         writeln!(f, "{}]", " ".repeat(indent))
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        nodes(&self.elements)
+    }
+}
+/*
+ * Tuple node
+ */
+impl Compilable for TupleNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let mut element_types = Vec::new();
+        for element in &self.elements {
+            element_types.push(element.compile(compiler)?);
+        }
+        compiler.out.push(Instructions::MakeTuple(self.elements.len()));
+        Ok(Tuple(element_types))
+    }
+
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}Tuple (", indent_fn(indent))?;
+        for element in &self.elements {
+            element.fmt_with_indent(f, indent + 1)?;
+        }
+        writeln!(f, "{})", indent_fn(indent))
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        nodes(&self.elements)
+    }
 }
+/*
+ * Map node
+ */
+impl Compilable for MapNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        // `{}` gets its key and value types from where it is used
+        let mut key_type = Void;
+        let mut value_type = Void;
+        for (key, value) in &self.entries {
+            let found = key.compile(compiler)?;
+            if !found.is_map_key() {
+                return Err(CompileError::InvalidMapKey {
+                    found,
+                    span: key.span().clone(),
+                });
+            }
+            if key_type == Void {
+                key_type = found;
+            } else if key_type != found {
+                return Err(TypeMismatch {
+                    expected: key_type,
+                    found,
+                    span: key.span().clone(),
+                });
+            }
+            let found = value.compile(compiler)?;
+            if value_type == Void {
+                value_type = found;
+            } else if let Some(unified) = value_type.unify(&found) {
+                value_type = unified;
+            } else {
+                return Err(TypeMismatch {
+                    expected: value_type,
+                    found,
+                    span: value.span().clone(),
+                });
+            }
+        }
+        compiler.out.push(Instructions::MakeMap(self.entries.len()));
+        Ok(Map(Box::new(key_type), Box::new(value_type)))
+    }
+
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}Map {{", indent_fn(indent))?;
+        for (key, value) in &self.entries {
+            key.fmt_with_indent(f, indent + 1)?;
+            value.fmt_with_indent(f, indent + 2)?;
+        }
+        writeln!(f, "{}}}", indent_fn(indent))
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        self.entries
+            .iter()
+            .flat_map(|(key, value)| [key.as_ref(), value.as_ref()])
+            .collect()
+    }
+}
+/*
+ * Index node
+ */
+impl Compilable for IndexNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        // Elements of a variable are read in place, without copying the whole array or map
+        let variable = self.object.assign_target().filter(|target| target.path.is_empty());
+        if let Some(name) = variable.map(|target| target.variable)
+            && let Some(var) = compiler.context.get_variable(&name)
+        {
+            let (key_type, element_type) = index_type(&var.value_type.clone(), &self.span)?;
+            compile_index(compiler, self.index.as_ref(), &key_type)?;
+            compiler.load_variable_index(&name);
+            return Ok(element_type);
+        }
+        let object_type = self.object.compile(compiler)?;
+        let (key_type, element_type) = index_type(&object_type, &self.span)?;
+        compile_index(compiler, self.index.as_ref(), &key_type)?;
+        compiler.out.push(Instructions::LoadIndex);
+        Ok(element_type)
+    }
+    fn assign_target(&self) -> Option<AssignTarget> {
+        let mut target = self.object.assign_target()?;
+        target.path.push(PathStep::Index(self.index.clone()));
+        target.span = self.span.clone();
+        Some(target)
+    }
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}Index", indent_fn(indent))?;
+        self.object.fmt_with_indent(f, indent + 1)?;
+        self.index.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.object.as_ref(), self.index.as_ref()]
+    }
+}
+
+/*
+ * Path assign node
+ */
+impl Compilable for PathAssignNode {
+    fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
+        let target = self
+            .target
+            .assign_target()
+            .ok_or_else(|| CompileError::NotAssignable {
+                span: self.target.span().clone(),
+            })?;
+        target.compile_store(compiler, false, |compiler, expected_type| {
+            let value_type = self.value.compile(compiler)?;
+            if !expected_type.accepts(&value_type) {
+                return Err(TypeMismatch {
+                    expected: expected_type,
+                    found: value_type,
+                    span: self.value.span().clone(),
+                });
+            }
+            Ok(())
+        })?;
+        Ok(Void)
+    }
+    fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{}Assign", indent_fn(indent))?;
+        self.target.fmt_with_indent(f, indent + 1)?;
+        self.value.fmt_with_indent(f, indent + 1)
+    }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        vec![self.target.as_ref(), self.value.as_ref()]
+    }
+}
+
+impl FunctionCallNode {
+    /// Puts positional and named arguments in parameter order,
+    /// `None` for a left out parameter the callee has a default for.
+    fn resolve_arguments<'a>(
+        &'a self,
+        params: &[FunctionArgs],
+    ) -> Result<Vec<Option<&'a dyn Compilable>>, CompileError> {
+        let mut slots: Vec<Option<&dyn Compilable>> =
+            self.args.iter().map(|arg| Some(arg.as_ref())).collect();
+        slots.resize(params.len(), None);
+
+        for named in &self.named_args {
+            let idx = params
+                .iter()
+                .position(|arg| arg.name == named.name)
+                .ok_or_else(|| CompileError::UnknownArgument {
+                    function: self.name.clone(),
+                    argument: named.name.clone(),
+                    span: named.span.clone(),
+                })?;
+            if slots[idx].is_some() {
+                return Err(CompileError::DuplicateArgument {
+                    argument: named.name.clone(),
+                    span: named.span.clone(),
+                });
+            }
+            slots[idx] = Some(named.value.as_ref());
+        }
+
+        slots
+            .into_iter()
+            .zip(params)
+            .map(|(slot, arg)| match slot {
+                None if arg.default.is_none() => Err(CompileError::MissingArgument {
+                    function: self.name.clone(),
+                    argument: arg.name.clone(),
+                    span: self.span.clone(),
+                }),
+                slot => Ok(slot),
+            })
+            .collect()
+    }
+    /// Pushes the arguments of the call in parameter order, checking their types.
+    /// Left out parameters get the default of the function at `address_name`.
+    pub fn compile_arguments(
+        &self,
+        compiler: &mut Compiler,
+        address_name: &str,
+        params: &[FunctionArgs],
+    ) -> Result<(), CompileError> {
+        if self.args.len() > params.len() {
+            return Err(CompileError::UnexpectedFunctionArguments {
+                name: self.name.clone(),
+                expected: params.len(),
+                found: self.args.len(),
+                span: self.span.clone(),
+            });
+        }
+        let arguments = self.resolve_arguments(params)?;
+        for (called_arg, fnc_arg) in arguments.iter().zip(params.iter()) {
+            // The default was checked against the parameter type with the definition
+            let Some(called_arg) = called_arg else {
+                compiler.call_function(&FunctionDefineNode::default_address(address_name, &fnc_arg.name), 0);
+                continue;
+            };
+            let called_args_type = called_arg.compile(compiler)?;
+            let final_fnc_type = compiler.context.get_type(&fnc_arg.argument_type, &fnc_arg.span)?;
+            if !final_fnc_type.accepts(&called_args_type) {
+                return Err(TypeMismatch {
+                    expected: final_fnc_type,
+                    found: called_args_type,
+                    span: called_arg.span().clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Infers the type parameters from the arguments and calls the instance for those types.
+    fn compile_generic_call(
+        &self,
+        compiler: &mut Compiler,
+        function: &CompileTimeFunctionForCheck,
+    ) -> Result<ComptimeValueType, CompileError> {
+        if self.args.len() > function.args.len() {
+            return Err(CompileError::UnexpectedFunctionArguments {
+                name: self.name.clone(),
+                expected: function.args.len(),
+                found: self.args.len(),
+                span: self.span.clone(),
+            });
+        }
+        let arguments = self.resolve_arguments(&function.args)?;
+        compiler
+            .context
+            .type_params
+            .push(FunctionDefineNode::signature_type_params(&function.type_params));
+        let params = function
+            .args
+            .iter()
+            .map(|arg| compiler.context.get_type(&arg.argument_type, &arg.span))
+            .collect::<Result<Vec<_>, _>>();
+        compiler.context.type_params.pop();
+
+        // The first argument a type parameter appears in decides its type
+        let mut bindings = HashMap::new();
+        let mut found_types = Vec::new();
+        // Calls of defaults, patched once the instance is known
+        let mut defaults = Vec::new();
+        for ((called_arg, param), arg) in arguments.iter().zip(params?).zip(&function.args) {
+            let Some(called_arg) = called_arg else {
+                defaults.push((compiler.out.len(), arg.name.clone()));
+                compiler.out.push(Instructions::Call(0, 0));
+                continue;
+            };
+            let found = called_arg.compile(compiler)?;
+            param.infer(&found, &mut bindings);
+            found_types.push((param, found, called_arg.span().clone()));
+        }
+        let mut types = Vec::new();
+        for param in &function.type_params {
+            let found = bindings.get(&param.name).ok_or_else(|| {
+                CompileError::CannotInferTypeParameter {
+                    name: param.name.clone(),
+                    function: self.name.clone(),
+                    span: self.span.clone(),
+                }
+            })?;
+            if let Some(constraint) = &param.constraint
+                && found.satisfies(constraint) != Some(true)
+            {
+                return Err(CompileError::UnsatisfiedConstraint {
+                    name: param.name.clone(),
+                    constraint: constraint.clone(),
+                    found: Box::new(found.clone()),
+                    span: self.span.clone(),
+                });
+            }
+            types.push(format!("{:?}", found));
+        }
+        for (param, found, span) in found_types {
+            let expected = param.substitute(&bindings);
+            if !expected.accepts(&found) {
+                return Err(TypeMismatch {
+                    expected,
+                    found,
+                    span,
+                });
+            }
+        }
+
+        let instance = format!("{}<{}>", self.name, types.join(", "));
+        let return_type = function.return_type.substitute(&bindings);
+        for (pos, param) in defaults {
+            compiler
+                .pending_calls
+                .push((pos, FunctionDefineNode::default_address(&instance, &param)));
+        }
+        compiler.instantiate(&self.name, instance.clone(), bindings);
+        compiler.call_function(&instance, function.args.len());
+        Ok(return_type)
+    }
+}
+
 impl Compilable for FunctionCallNode {
     fn compile(&self, compiler: &mut Compiler) -> Result<ComptimeValueType, CompileError> {
         match self.call_type {
             CallType::Macro => {
+                if let Some(named) = self.named_args.first() {
+                    return Err(CompileError::UnknownArgument {
+                        function: format!("{}!", self.name),
+                        argument: named.name.clone(),
+                        span: named.span.clone(),
+                    });
+                }
                 let mac = compiler.macros.macros.remove(&self.name).ok_or(
                     CompileError::UnknownMacro {
                         name: self.name.clone(),
+                        span: self.span.clone(),
                     },
                 )?;
-                let result = mac.compile(compiler, &self.args);
+                let result = mac.compile(compiler, &self.args, &self.span);
                 compiler.macros.macros.insert(self.name.clone(), mac);
                 result
             }
             CallType::Fn => {
-                let called_function:CompileTimeFunctionForCheck = compiler.context.get_fn(&self.name)?;
-                if self.args.len()!=called_function.args.len() {
-                    return Err(CompileError::UnexpectedFunctionArguments { name: self.name.clone(), expected: called_function.args.len(), found: self.args.len() });
+                // Variables holding a function shadow named functions
+                if let Some(var) = compiler.context.get_variable(&self.name) {
+                    let callee_type = var.value_type.clone();
+                    compiler.load_variable(&self.name);
+                    return compile_value_call(
+                        compiler,
+                        callee_type,
+                        &self.name,
+                        &self.args,
+                        &self.named_args,
+                        &self.span,
+                    );
                 }
-                compiler.context.enter_scope();
-                for (called_arg, fnc_arg) in self.args.iter().zip(called_function.args.iter()) {
-                    let called_args_type = called_arg.compile(compiler)?;
-                    compiler.out.push(Instructions::SaveVar(fnc_arg.name.clone()));
-                    let final_fnc_type = CompileContext::get_type(&fnc_arg.argument_type)?;
-                    if called_args_type != final_fnc_type {
-                        return Err(TypeMismatch { expected: final_fnc_type, found: called_args_type });
-                       
-                    }
-                    compiler.context.add_variable(fnc_arg.name.clone(), ComptimeVariable { value_type: called_args_type, is_const: false })?; 
+                let called_function: CompileTimeFunctionForCheck =
+                    compiler.context.get_fn(&self.name, &self.span)?;
+                if called_function.is_generic() {
+                    return self.compile_generic_call(compiler, &called_function);
                 }
-                for statement in called_function.body  {
-                    statement.compile(compiler)?;
-                    
-                }
-                compiler.context.exit_scope(); 
-                Ok(Void)
+                self.compile_arguments(compiler, &self.name, &called_function.args)?;
+                compiler.call_function(&self.name, called_function.args.len());
+                Ok(called_function.return_type)
             }
         }
     }
     fn fmt_with_indent(&self, _f: &mut Formatter<'_>, _indent: usize) -> fmt::Result {
         writeln!(_f, "{}{}(...)", indent_fn(_indent), self.name)
     }
+    fn span(&self) -> &Span {
+        &self.span
+    }
+    fn children(&self) -> Vec<&dyn Compilable> {
+        let mut children = nodes(&self.args);
+        children.extend(self.named_args.iter().map(|arg| arg.value.as_ref()));
+        children
+    }
+    fn calls_function(&self) -> bool {
+        matches!(self.call_type, CallType::Fn)
+    }
 }
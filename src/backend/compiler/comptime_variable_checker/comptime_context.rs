@@ -1,34 +1,125 @@
 use crate::backend::compiler::comptime_variable_checker::comptime_value_for_check::ComptimeValueType;
 use crate::backend::compiler::functions_compiler_context::CompileTimeFunctionForCheck;
+use crate::backend::compiler::structs_compiler_context::CompileTimeStructForCheck;
+use crate::backend::compiler::enums_compiler_context::CompileTimeEnumForCheck;
 use crate::backend::errors::compiler::compiler_errors::CompileError;
 use crate::backend::compiler::comptime_variable_checker::comptime_value_for_check::ComptimeValueType::{
-    Bool, Int, StringValue, Void, Float
+    Array, Bool, Int, Map, StringValue, Tuple, Void, Float, Struct, Enum, Optional, Function
 };
 use crate::backend::errors::compiler::compiler_errors::CompileError::UndefinedType;
+use crate::backend::lexer::tokens::Span;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
 pub struct CompileContext {
     pub variables: HashMap<String, ComptimeVariable>,
     pub functions: Vec<HashMap<String, CompileTimeFunctionForCheck>>,
-    pub scopes:Vec<HashMap<String,ComptimeVariable>>
+    pub scopes:Vec<HashMap<String,ComptimeVariable>>,
+    pub structs: HashMap<String, CompileTimeStructForCheck>,
+    pub enums: HashMap<String, CompileTimeEnumForCheck>,
+    /// Functions defined in `impl` blocks, by type name and then function name
+    pub methods: HashMap<String, HashMap<String, CompileTimeFunctionForCheck>>,
+    /// Index into `scopes` where the body of each function being compiled starts
+    pub function_scopes:Vec<usize>,
+    /// Types the type parameters of the generic function being compiled stand for
+    pub type_params:Vec<HashMap<String,ComptimeValueType>>,
+}
+impl Default for CompileContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 impl CompileContext {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
             functions: vec![HashMap::new()],
-            scopes:vec![HashMap::new()]
+            scopes:vec![HashMap::new()],
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            methods: HashMap::new(),
+            function_scopes:Vec::new(),
+            type_params:Vec::new(),
         }
     }
-    pub fn get_type(type_to_identify: &str) -> Result<ComptimeValueType, CompileError> {
+    pub fn get_type(&self, type_to_identify: &str, span: &Span) -> Result<ComptimeValueType, CompileError> {
+        if let Some(bound) = self.type_params.last().and_then(|params| params.get(type_to_identify)) {
+            return Ok(bound.clone());
+        }
         match type_to_identify {
             "numb" => Ok(Int),
             "string" => Ok(StringValue),
             "bool" => Ok(Bool),
             "void" => Ok(Void),
             "flt" => Ok(Float),
+            _ if self.structs.contains_key(type_to_identify) => {
+                Ok(Struct(type_to_identify.to_string()))
+            }
+            _ if self.enums.contains_key(type_to_identify) => {
+                Ok(Enum(type_to_identify.to_string()))
+            }
+            // Checked before the suffixes, `fnc(): numb[]` returns an array
+            _ if type_to_identify.starts_with("fnc(") => {
+                let undefined = || UndefinedType {
+                    undefined_type: type_to_identify.to_string(),
+                    span: span.clone(),
+                };
+                let params_end = closing_paren(type_to_identify, 3).ok_or_else(undefined)?;
+                let params = match type_to_identify[4..params_end].trim() {
+                    "" => Vec::new(),
+                    params => split_type_list(params)
+                        .into_iter()
+                        .map(|param| self.get_type(param, span))
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+                let return_type = match type_to_identify[params_end + 1..].trim() {
+                    "" => Void,
+                    rest => self.get_type(rest.strip_prefix(':').ok_or_else(undefined)?.trim(), span)?,
+                };
+                Ok(Function(params, Box::new(return_type)))
+            }
+            _ if type_to_identify.ends_with('?') => {
+                let inner = &type_to_identify[..type_to_identify.len() - 1];
+                // `numb??` is the same as `numb?`
+                match self.get_type(inner, span)? {
+                    Optional(inner) => Ok(Optional(inner)),
+                    inner => Ok(Optional(Box::new(inner))),
+                }
+            }
+            _ if type_to_identify.ends_with("[]") => {
+                let element = &type_to_identify[..type_to_identify.len() - 2];
+                Ok(Array(Box::new(self.get_type(element, span)?)))
+            }
+            _ if type_to_identify.starts_with("map<") && type_to_identify.ends_with('>') => {
+                let inner = &type_to_identify[4..type_to_identify.len() - 1];
+                let [key, value] = split_type_list(inner)[..] else {
+                    return Err(UndefinedType {
+                        undefined_type: type_to_identify.to_string(),
+                        span: span.clone(),
+                    });
+                };
+                let key = self.get_type(key, span)?;
+                if !key.is_map_key() {
+                    return Err(CompileError::InvalidMapKey { found: key, span: span.clone() });
+                }
+                let value = self.get_type(value, span)?;
+                Ok(Map(Box::new(key), Box::new(value)))
+            }
+            _ if type_to_identify.starts_with('(') && type_to_identify.ends_with(')') => {
+                let inner = &type_to_identify[1..type_to_identify.len() - 1];
+                let mut elements = split_type_list(inner)
+                    .into_iter()
+                    .map(|element| self.get_type(element, span))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // `(fnc(): numb)[]` only uses the parentheses for grouping
+                match elements.len() {
+                    1 => Ok(elements.remove(0)),
+                    _ => Ok(Tuple(elements)),
+                }
+            }
             _ => Err(UndefinedType {
                 undefined_type: type_to_identify.to_string(),
+                span: span.clone(),
             }),
         }
     }
@@ -39,42 +130,203 @@ impl CompileContext {
         self.scopes.push(HashMap::new());
         
     }
-    pub fn add_variable(&mut self,name : String,variable:ComptimeVariable)->Result<(),CompileError> {
+    pub fn add_variable(&mut self,name : String,variable:ComptimeVariable,span:&Span)->Result<(),CompileError> {
         let current_scope = self.scopes.last_mut().unwrap();
-        if current_scope.contains_key(&name){
-            return Err(CompileError::VariableRecreation { name })
-        }else {
-            current_scope.insert(name, variable);
-            Ok(())
+        match current_scope.entry(name) {
+            Entry::Occupied(entry) => Err(CompileError::VariableRecreation {
+                name: entry.key().clone(),
+                span: span.clone(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(variable);
+                Ok(())
+            }
         }
     }
-    pub fn get_variable(&self,name:&str)->Option<&ComptimeVariable>{
-        for scope in self.scopes.iter().rev() {
+    /// Starts the scope of a function body, locals of enclosing functions are not visible in it.
+    pub fn enter_function(&mut self) {
+        self.function_scopes.push(self.scopes.len());
+        self.enter_scope();
+    }
+    pub fn exit_function(&mut self) {
+        self.exit_scope();
+        self.function_scopes.pop();
+    }
+    fn find_variable(&self,name:&str)->Option<(usize,&ComptimeVariable)>{
+        let function_start = self.function_scopes.last().copied().unwrap_or(0);
+        let globals_end = self.function_scopes.first().copied().unwrap_or(self.scopes.len());
+        for (idx, scope) in self.scopes.iter().enumerate().rev() {
+            if idx < function_start && idx >= globals_end {
+                continue;
+            }
             if let Some(v) = scope.get(name) {
-                return Some(v);
+                return Some((idx, v));
             }
         }
         None
-
     }
-    pub fn add_function(&mut self,name : String,fnc:CompileTimeFunctionForCheck)->Result<(),CompileError> {
+    pub fn get_variable(&self,name:&str)->Option<&ComptimeVariable>{
+        self.find_variable(name).map(|(_, v)| v)
+    }
+    /// Name a visible variable is stored under at runtime.
+    pub fn storage_name(&self,name:&str)->String{
+        match self.get_variable(name).and_then(|var| var.storage.as_ref()) {
+            Some(storage) => storage.clone(),
+            None => name.to_string(),
+        }
+    }
+    /// Variables of the function being compiled that are visible here, for closures to capture,
+    /// as their name, type and storage name.
+    pub fn local_variables(&self)->Vec<(String,ComptimeValueType,Option<String>)>{
+        let Some(function_start) = self.function_scopes.last().copied() else {
+            return Vec::new();
+        };
+        let mut locals: Vec<(String, ComptimeValueType, Option<String>)> = Vec::new();
+        for scope in self.scopes[function_start..].iter().rev() {
+            for (name, var) in scope {
+                if !locals.iter().any(|(known, _, _)| known == name) {
+                    locals.push((name.clone(), var.value_type.clone(), var.storage.clone()));
+                }
+            }
+        }
+        locals
+    }
+    /// Changes the type of a visible variable in place and returns the old one.
+    /// Used to narrow `numb?` to `numb` inside of an `undef` check.
+    pub fn retype_variable(&mut self,name:&str,value_type:ComptimeValueType)->Option<ComptimeValueType>{
+        let (idx, _) = self.find_variable(name)?;
+        let var = self.scopes[idx].get_mut(name)?;
+        Some(std::mem::replace(&mut var.value_type, value_type))
+    }
+    /// Whether `name` is a top level variable, which functions can change.
+    pub fn is_top_level(&self,name:&str)->bool{
+        match (self.find_variable(name), self.function_scopes.first()) {
+            (Some((idx, _)), Some(globals_end)) => idx < *globals_end,
+            (found, None) => found.is_some(),
+            _ => false,
+        }
+    }
+    /// Whether `name` is a top level variable accessed from inside a function.
+    pub fn is_global(&self,name:&str)->bool{
+        match (self.find_variable(name), self.function_scopes.first()) {
+            (Some((idx, _)), Some(globals_end)) => idx < *globals_end,
+            _ => false,
+        }
+    }
+    pub fn add_function(&mut self,name : String,fnc:CompileTimeFunctionForCheck,span:&Span)->Result<(),CompileError> {
         let curren_fnc_scope = self.functions.last_mut().unwrap();
-        if curren_fnc_scope.contains_key(&name) {
-            return Err(CompileError::FunctionAlredyExists { name });
-            
+        match curren_fnc_scope.entry(name) {
+            Entry::Occupied(entry) => Err(CompileError::FunctionAlredyExists {
+                name: entry.key().clone(),
+                span: span.clone(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(fnc);
+                Ok(())
+            }
+        }
+    }
+    pub fn add_struct(&mut self,name : String,definition:CompileTimeStructForCheck,span:&Span)->Result<(),CompileError> {
+        if self.enums.contains_key(&name) {
+            return Err(CompileError::TypeAlreadyExists { name, span: span.clone() });
+        }
+        match self.structs.entry(name) {
+            Entry::Occupied(entry) => Err(CompileError::TypeAlreadyExists {
+                name: entry.key().clone(),
+                span: span.clone(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(definition);
+                Ok(())
+            }
         }
-        else {
-            curren_fnc_scope.insert(name, fnc);
-            Ok(())
+    }
+    pub fn get_struct(&self,name:&str,span:&Span)->Result<CompileTimeStructForCheck,CompileError> {
+        self.structs.get(name).cloned().ok_or(UndefinedType { undefined_type: name.to_string(), span: span.clone() })
+    }
+    pub fn add_enum(&mut self,name : String,definition:CompileTimeEnumForCheck,span:&Span)->Result<(),CompileError> {
+        if self.structs.contains_key(&name) {
+            return Err(CompileError::TypeAlreadyExists { name, span: span.clone() });
         }
+        match self.enums.entry(name) {
+            Entry::Occupied(entry) => Err(CompileError::TypeAlreadyExists {
+                name: entry.key().clone(),
+                span: span.clone(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(definition);
+                Ok(())
+            }
+        }
+    }
+    pub fn get_enum(&self,name:&str,span:&Span)->Result<CompileTimeEnumForCheck,CompileError> {
+        self.enums.get(name).cloned().ok_or(UndefinedType { undefined_type: name.to_string(), span: span.clone() })
+    }
+    pub fn add_method(&mut self,type_name:&str,name : String,fnc:CompileTimeFunctionForCheck,span:&Span)->Result<(),CompileError> {
+        match self.methods.entry(type_name.to_string()).or_default().entry(name) {
+            Entry::Occupied(entry) => Err(CompileError::FunctionAlredyExists {
+                name: format!("{}.{}", type_name, entry.key()),
+                span: span.clone(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(fnc);
+                Ok(())
+            }
+        }
+    }
+    pub fn get_method(&self,type_name:&str,name:&str,span:&Span)->Result<CompileTimeFunctionForCheck,CompileError> {
+        self.methods
+            .get(type_name)
+            .and_then(|methods| methods.get(name))
+            .cloned()
+            .ok_or(CompileError::UnknownMethod {
+                type_name: type_name.to_string(),
+                name: name.to_string(),
+                span: span.clone(),
+            })
+    }
+    pub fn get_fn(&mut self,name:&str,span:&Span)->Result<CompileTimeFunctionForCheck,CompileError> {
+            self.functions.last_mut().unwrap().get(name).cloned().ok_or(CompileError::UnknownFunction { name: name.to_string(), span: span.clone() })        
+    }
+}
 
+/// Index of the `)` closing the `(` at `open`.
+fn closing_paren(types: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in types.char_indices().skip(open) {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(idx),
+            ')' => depth -= 1,
+            _ => {}
+        }
     }
-    pub fn get_fn(&mut self,name:&str)->Result<CompileTimeFunctionForCheck,CompileError> {
-            self.functions.last_mut().unwrap().get(name).cloned().ok_or(CompileError::UnknownFunction { name: name.to_string() })        
+    None
+}
+
+/// Splits `string, map<string, numb>` at the commas that aren't part of a nested type.
+fn split_type_list(types: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, c) in types.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(types[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
     }
+    parts.push(types[start..].trim());
+    parts
 }
 
 pub struct ComptimeVariable {
     pub value_type: ComptimeValueType,
     pub is_const: bool,
+    /// Name the value lives under at runtime, when it isn't the name in the source
+    pub storage: Option<String>,
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ComptimeValueType {
     Int,
@@ -6,4 +8,144 @@ pub enum ComptimeValueType {
     Void,
     Float,
     Array(Box<ComptimeValueType>),
+    /// Map from keys of the first type to values of the second
+    Map(Box<ComptimeValueType>, Box<ComptimeValueType>),
+    /// Fixed number of values with their own types, like `(numb, string)`
+    Tuple(Vec<ComptimeValueType>),
+    /// Instance of a user defined struct, by name
+    Struct(String),
+    /// Value of a user defined enum, by name
+    Enum(String),
+    /// Value that may be `undef`, like `numb?`. `Optional(Void)` is the type of `undef` itself
+    Optional(Box<ComptimeValueType>),
+    /// Function value with its parameter types and return type, like `fnc(numb): numb`
+    Function(Vec<ComptimeValueType>, Box<ComptimeValueType>),
+    /// Type parameter of a generic function, like `T` in `fnc max<T>(a: T, b: T): T`
+    TypeParam(String),
+}
+impl ComptimeValueType {
+    /// Whether a value of type `found` can be stored where `self` is expected.
+    pub fn accepts(&self, found: &ComptimeValueType) -> bool {
+        match (self, found) {
+            // `[]` has no element type of its own and fits any array, also inside other literals
+            (ComptimeValueType::Array(expected), ComptimeValueType::Array(element)) => {
+                **element == ComptimeValueType::Void || expected.accepts(element)
+            }
+            // The same goes for `{}` and maps
+            (ComptimeValueType::Map(expected_key, expected), ComptimeValueType::Map(key, value)) => {
+                **key == ComptimeValueType::Void || (expected_key == key && expected.accepts(value))
+            }
+            (ComptimeValueType::Tuple(expected), ComptimeValueType::Tuple(elements)) => {
+                expected.len() == elements.len()
+                    && expected.iter().zip(elements).all(|(e, found)| e.accepts(found))
+            }
+            // `numb?` takes a `numb`, `undef` or another `numb?`
+            (ComptimeValueType::Optional(expected), ComptimeValueType::Optional(inner)) => {
+                **inner == ComptimeValueType::Void || expected.accepts(inner)
+            }
+            (ComptimeValueType::Optional(expected), _) => expected.accepts(found),
+            _ => self == found,
+        }
+    }
+    /// Type that holds the values of both types, used for the elements of literals.
+    pub fn unify(&self, other: &ComptimeValueType) -> Option<ComptimeValueType> {
+        if self.accepts(other) {
+            return Some(self.clone());
+        }
+        if other.accepts(self) {
+            return Some(other.clone());
+        }
+        // `[1, undef]` is a `numb?[]`
+        match (self, other) {
+            (ComptimeValueType::Optional(inner), value) | (value, ComptimeValueType::Optional(inner))
+                if **inner == ComptimeValueType::Void =>
+            {
+                Some(ComptimeValueType::Optional(Box::new(value.clone())))
+            }
+            _ => None,
+        }
+    }
+    /// Type of the value once it is known not to be `undef`.
+    pub fn defined(&self) -> &ComptimeValueType {
+        match self {
+            ComptimeValueType::Optional(inner) => inner,
+            _ => self,
+        }
+    }
+    /// Binds the type parameters in `self` to the matching parts of `found`, like `T[]` and
+    /// `numb[]` give `T = numb`. Parameters that are already bound are left as they are.
+    pub fn infer(&self, found: &ComptimeValueType, bindings: &mut HashMap<String, ComptimeValueType>) {
+        match (self, found) {
+            // `undef`, `[]` and `{}` say nothing about the type
+            (ComptimeValueType::TypeParam(_), ComptimeValueType::Void) => {}
+            (ComptimeValueType::TypeParam(_), ComptimeValueType::Optional(inner))
+                if **inner == ComptimeValueType::Void => {}
+            (ComptimeValueType::TypeParam(name), _) => {
+                bindings.entry(name.clone()).or_insert_with(|| found.clone());
+            }
+            (ComptimeValueType::Array(expected), ComptimeValueType::Array(element)) => {
+                expected.infer(element, bindings)
+            }
+            (ComptimeValueType::Map(expected_key, expected), ComptimeValueType::Map(key, value)) => {
+                expected_key.infer(key, bindings);
+                expected.infer(value, bindings);
+            }
+            (ComptimeValueType::Tuple(expected), ComptimeValueType::Tuple(elements)) => {
+                for (e, found) in expected.iter().zip(elements) {
+                    e.infer(found, bindings);
+                }
+            }
+            (ComptimeValueType::Optional(expected), ComptimeValueType::Optional(inner)) => {
+                expected.infer(inner, bindings)
+            }
+            (ComptimeValueType::Optional(expected), _) => expected.infer(found, bindings),
+            (
+                ComptimeValueType::Function(expected_params, expected_return),
+                ComptimeValueType::Function(params, return_type),
+            ) => {
+                for (e, found) in expected_params.iter().zip(params) {
+                    e.infer(found, bindings);
+                }
+                expected_return.infer(return_type, bindings);
+            }
+            _ => {}
+        }
+    }
+    /// Replaces the bound type parameters in `self` with their types.
+    pub fn substitute(&self, bindings: &HashMap<String, ComptimeValueType>) -> ComptimeValueType {
+        let sub = |t: &ComptimeValueType| Box::new(t.substitute(bindings));
+        match self {
+            ComptimeValueType::TypeParam(name) => bindings.get(name).cloned().unwrap_or_else(|| self.clone()),
+            ComptimeValueType::Array(element) => ComptimeValueType::Array(sub(element)),
+            ComptimeValueType::Map(key, value) => ComptimeValueType::Map(sub(key), sub(value)),
+            ComptimeValueType::Tuple(elements) => {
+                ComptimeValueType::Tuple(elements.iter().map(|e| e.substitute(bindings)).collect())
+            }
+            ComptimeValueType::Optional(inner) => ComptimeValueType::Optional(sub(inner)),
+            ComptimeValueType::Function(params, return_type) => ComptimeValueType::Function(
+                params.iter().map(|p| p.substitute(bindings)).collect(),
+                sub(return_type),
+            ),
+            _ => self.clone(),
+        }
+    }
+    /// Whether the type meets a type parameter constraint, `None` for unknown constraints.
+    pub fn satisfies(&self, constraint: &str) -> Option<bool> {
+        match constraint {
+            "numeric" => Some(matches!(self, ComptimeValueType::Int | ComptimeValueType::Float)),
+            // What `write!` and `writeLn!` take
+            "printable" => Some(matches!(
+                self,
+                ComptimeValueType::Int
+                    | ComptimeValueType::Float
+                    | ComptimeValueType::StringValue
+                    | ComptimeValueType::Bool
+            )),
+            _ => None,
+        }
+    }
+    /// Whether values of this type can be used as map keys.
+    pub fn is_map_key(&self) -> bool {
+        matches!(self, ComptimeValueType::Int | ComptimeValueType::StringValue)
+    }
 }
@@ -0,0 +1,11 @@
+use crate::backend::ast::statements::enums::EnumVariant;
+#[derive(Clone)]
+pub struct CompileTimeEnumForCheck{
+    pub variants:Vec<EnumVariant>
+}
+impl CompileTimeEnumForCheck {
+    /// Tag of a variant in the runtime value and its definition.
+    pub fn variant(&self, name: &str) -> Option<(usize, &EnumVariant)> {
+        self.variants.iter().enumerate().find(|(_, variant)| variant.name == name)
+    }
+}
@@ -1,12 +1,20 @@
-use crate::backend::ast::statements::functions::args_node::FunctionArgs;
-use crate::backend::compiler::byte_code::Compilable;
+use crate::backend::ast::statements::functions::args_node::{FunctionArgs, TypeParam};
 use crate::backend::compiler::comptime_variable_checker::comptime_value_for_check::ComptimeValueType;
 #[derive(Clone)]
 pub struct CompileTimeFunctionForCheck{
     pub return_type:ComptimeValueType,
     pub is_pub:bool,
-    pub body:Vec<Box<dyn Compilable>>,
-    pub args:Vec<FunctionArgs>
+    pub args:Vec<FunctionArgs>,
+    /// Type parameters of a generic function, the argument and return types may use them
+    pub type_params:Vec<TypeParam>,
 
 }
-
+impl CompileTimeFunctionForCheck {
+    pub fn is_generic(&self) -> bool {
+        !self.type_params.is_empty()
+    }
+    /// Whether this is a method called on a value, which is passed as the first argument.
+    pub fn takes_self(&self) -> bool {
+        self.args.first().is_some_and(|arg| arg.name == "self")
+    }
+}
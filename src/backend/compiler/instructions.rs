@@ -4,11 +4,16 @@ pub const  SUB:u8 = 2;
 pub const  DIV:u8 = 3;
 pub const  MUL:u8 = 4;
 pub const  MODULO:u8 = 5;
+pub const NEGATE:u8 = 13;
 
 //COMP
 pub const GREATER:u8 = 6;
 pub const LESS:u8 = 7;
 pub const EQUAL:u8 = 8;
+pub const NOT_EQUAL:u8 = 9;
+pub const LESS_EQUAL:u8 = 10;
+pub const GREATER_EQUAL:u8 = 11;
+pub const NOT:u8 = 12;
 //JUMPS
 pub const JUMP:u8 =40;
 pub const JUMP_IF_FALSE:u8 = 41;
@@ -18,6 +23,8 @@ pub const JUMP_IF_TRUE:u8 = 42;
 pub const  PUSH_STR:u8 = 20;
 pub const  PUSH_BOOL:u8 = 21;
 pub const PUSH_NUMB:u8 = 22;
+pub const TO_STRING:u8 = 23;
+pub const POP:u8 = 24;
 
 // IO
 pub const WRITE_LN:u8 = 30;
@@ -29,6 +36,57 @@ pub const PROCESS_EXIT:u8 = 61;
 // VARS
 pub const STORE_VAR:u8 = 50;
 pub const LOAD_VAR:u8 = 51;
+pub const STORE_GLOBAL:u8 = 52;
+pub const LOAD_GLOBAL:u8 = 53;
+pub const LOAD_VAR_INDEX:u8 = 54;
+pub const STORE_VAR_INDEX:u8 = 55;
+pub const PUSH_VAR:u8 = 56;
+pub const LOAD_GLOBAL_INDEX:u8 = 57;
+pub const STORE_GLOBAL_INDEX:u8 = 58;
+pub const PUSH_GLOBAL:u8 = 59;
+
+// FUNCTIONS
+pub const CALL:u8 = 70;
+pub const RETURN:u8 = 71;
+pub const RETURN_VALUE:u8 = 72;
+
+// STRUCTS
+pub const MAKE_STRUCT:u8 = 80;
+pub const LOAD_FIELD:u8 = 81;
+pub const STORE_FIELD:u8 = 82;
+
+// ARRAYS
+pub const MAKE_ARRAY:u8 = 90;
+pub const LOAD_INDEX:u8 = 91;
+pub const STORE_INDEX:u8 = 92;
+pub const LEN:u8 = 93;
+pub const ARRAY_PUSH:u8 = 94;
+
+// ENUMS
+pub const MAKE_ENUM:u8 = 100;
+pub const IS_VARIANT:u8 = 101;
+pub const LOAD_PAYLOAD:u8 = 102;
+
+// MAPS
+pub const MAKE_MAP:u8 = 110;
+pub const MAP_HAS:u8 = 111;
+pub const MAP_REMOVE:u8 = 112;
+pub const MAP_KEYS:u8 = 113;
+
+// TUPLES
+pub const MAKE_TUPLE:u8 = 120;
+
+// OPTIONALS
+pub const PUSH_UNDEF:u8 = 130;
+
+// ERRORS
+pub const PUSH_HANDLER:u8 = 140;
+pub const POP_HANDLER:u8 = 141;
+pub const THROW:u8 = 142;
+
+// CLOSURES
+pub const MAKE_CLOSURE:u8 = 150;
+pub const CALL_INDIRECT:u8 = 151;
 
 
 
@@ -41,17 +99,38 @@ pub enum Instructions {
     Div,
     Mul ,
     Modulo ,
+    Negate,
     //Comparison
     GreaterThan,
     LessThan ,
     Equal ,
+    NotEqual,
+    LessEqual,
+    GreaterEqual,
+    Not,
     //Variables
     LoadVar(String),
     SaveVar(String),
+    LoadGlobal(String),
+    SaveGlobal(String),
+    /// Pops an index or key, pushes that element of the array or map in the variable
+    LoadVarIndex(String),
+    /// Pops an index or key and a value, replaces that element of the variable in place
+    StoreVarIndex(String),
+    /// Pops a value and appends it to the array in the variable in place
+    PushVar(String),
+    /// `LoadVarIndex` on a global
+    LoadGlobalIndex(String),
+    /// `StoreVarIndex` on a global
+    StoreGlobalIndex(String),
+    /// `PushVar` on a global
+    PushGlobal(String),
     //Values
     PushString(String),
     PushBool(bool),
     PushNumber(f32),
+    ToString,
+    Pop,
     ReadInput,
     //Printing
     WriteLnLastOnStack,
@@ -62,7 +141,65 @@ pub enum Instructions {
     Jump(usize),
     JumpIfFalse(usize),
     JumpIfTrue(usize),
-
+    //Functions
+    /// Jumps to the function at the address, the arguments are the top `argc` stack values
+    Call(usize, usize),
+    Return,
+    /// Returns the top of the stack to the caller
+    ReturnValue,
+    //Structs
+    /// Builds a struct from the top `count` stack values, the first field is the deepest
+    MakeStruct(String, usize),
+    /// Replaces the struct or tuple on top of the stack with one of its fields
+    LoadField(usize),
+    /// Pops a value and a struct or tuple, pushes it with the field set to the value
+    StoreField(usize),
+    //Arrays
+    /// Builds an array from the top `count` stack values, the first element is the deepest
+    MakeArray(usize),
+    /// Pops an index and an array or a key and a map, pushes the element
+    LoadIndex,
+    /// Pops an index, a value and an array, pushes the array with the element replaced.
+    /// With a key and a map the entry is inserted or replaced
+    StoreIndex,
+    /// Replaces the array, map or string on top of the stack with its length
+    Len,
+    /// Pops a value and an array, pushes the array with the value appended
+    ArrayPush,
+    //Enums
+    /// Builds a variant with the given tag from the top `count` stack values, the first one is the deepest
+    MakeEnum(String, usize, usize),
+    /// Replaces the enum on top of the stack with whether it has the given tag
+    IsVariant(usize),
+    /// Replaces the enum on top of the stack with one of its payload values
+    LoadPayload(usize),
+    //Maps
+    /// Builds a map from the top `count` key and value pairs, the first key is the deepest
+    MakeMap(usize),
+    /// Pops a key and a map, pushes whether the map has the key
+    MapHas,
+    /// Pops a key and a map, pushes the map without the key
+    MapRemove,
+    /// Replaces the map on top of the stack with an array of its keys
+    MapKeys,
+    //Tuples
+    /// Builds a tuple from the top `count` stack values, the first element is the deepest
+    MakeTuple(usize),
+    //Optionals
+    /// Pushes `undef`, the value of an optional that holds nothing
+    PushUndef,
+    //Errors
+    /// Starts a `try` block, errors jump to the `catch` at the address
+    PushHandler(usize),
+    /// Ends the innermost `try` block
+    PopHandler,
+    /// Pops an error message and unwinds to the innermost `catch`
+    Throw,
+    //Closures
+    /// Pushes a function value for the code at the address, with copies of the named locals
+    MakeClosure(usize, Vec<String>),
+    /// Pops `argc` arguments and the function value below them, then calls it
+    CallIndirect(usize),
 
     // Halt
     Halt,
@@ -76,16 +213,23 @@ impl Instructions {
             Instructions::Div => DIV,
             Instructions::Mul => MUL,
             Instructions::Modulo => MODULO,
+            Instructions::Negate => NEGATE,
 
             Instructions::GreaterThan => GREATER,
             Instructions::LessThan => LESS,
             Instructions::Equal => EQUAL,
+            Instructions::NotEqual => NOT_EQUAL,
+            Instructions::LessEqual => LESS_EQUAL,
+            Instructions::GreaterEqual => GREATER_EQUAL,
+            Instructions::Not => NOT,
 
 
 
             Instructions::PushString(_) => PUSH_STR,
             Instructions::PushBool(_) => PUSH_BOOL,
             Instructions::PushNumber(_) => PUSH_NUMB,
+            Instructions::ToString => TO_STRING,
+            Instructions::Pop => POP,
 
             Instructions::WriteLnLastOnStack => WRITE_LN,
             Instructions::WriteLastOnStack => WRITE,
@@ -95,6 +239,48 @@ impl Instructions {
 
             Instructions::LoadVar(_) => LOAD_VAR,
             Instructions::SaveVar(_) => STORE_VAR,
+            Instructions::LoadGlobal(_) => LOAD_GLOBAL,
+            Instructions::SaveGlobal(_) => STORE_GLOBAL,
+            Instructions::LoadVarIndex(_) => LOAD_VAR_INDEX,
+            Instructions::StoreVarIndex(_) => STORE_VAR_INDEX,
+            Instructions::PushVar(_) => PUSH_VAR,
+            Instructions::LoadGlobalIndex(_) => LOAD_GLOBAL_INDEX,
+            Instructions::StoreGlobalIndex(_) => STORE_GLOBAL_INDEX,
+            Instructions::PushGlobal(_) => PUSH_GLOBAL,
+
+            Instructions::Call(_, _) => CALL,
+            Instructions::Return => RETURN,
+            Instructions::ReturnValue => RETURN_VALUE,
+
+            Instructions::MakeStruct(_, _) => MAKE_STRUCT,
+            Instructions::LoadField(_) => LOAD_FIELD,
+            Instructions::StoreField(_) => STORE_FIELD,
+
+            Instructions::MakeArray(_) => MAKE_ARRAY,
+            Instructions::LoadIndex => LOAD_INDEX,
+            Instructions::StoreIndex => STORE_INDEX,
+            Instructions::Len => LEN,
+            Instructions::ArrayPush => ARRAY_PUSH,
+
+            Instructions::MakeEnum(_, _, _) => MAKE_ENUM,
+            Instructions::IsVariant(_) => IS_VARIANT,
+            Instructions::LoadPayload(_) => LOAD_PAYLOAD,
+
+            Instructions::MakeMap(_) => MAKE_MAP,
+            Instructions::MapHas => MAP_HAS,
+            Instructions::MapRemove => MAP_REMOVE,
+            Instructions::MapKeys => MAP_KEYS,
+
+            Instructions::MakeTuple(_) => MAKE_TUPLE,
+
+            Instructions::PushUndef => PUSH_UNDEF,
+
+            Instructions::PushHandler(_) => PUSH_HANDLER,
+            Instructions::PopHandler => POP_HANDLER,
+            Instructions::Throw => THROW,
+
+            Instructions::MakeClosure(_, _) => MAKE_CLOSURE,
+            Instructions::CallIndirect(_) => CALL_INDIRECT,
 
             Instructions::Jump(_) => JUMP,
             Instructions::JumpIfFalse(_) => JUMP_IF_FALSE,
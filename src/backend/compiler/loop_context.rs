@@ -0,0 +1,33 @@
+use crate::backend::compiler::instructions::Instructions;
+
+/// Jumps of one loop that are waiting for the loop to finish compiling,
+/// because their targets are not known yet.
+#[derive(Debug, Clone)]
+pub struct LoopContext {
+    pub label: Option<String>,
+    pub break_jumps: Vec<usize>,
+    pub continue_jumps: Vec<usize>,
+    /// Number of `try` blocks around the loop, the ones inside it end on `break`/`continue`
+    pub try_depth: usize,
+}
+
+impl LoopContext {
+    pub fn new(label: Option<String>, try_depth: usize) -> Self {
+        Self {
+            label,
+            break_jumps: Vec::new(),
+            continue_jumps: Vec::new(),
+            try_depth,
+        }
+    }
+
+    /// Points every pending `continue` and `break` jump at its target.
+    pub fn patch(self, out: &mut [Instructions], continue_target: usize, break_target: usize) {
+        for pos in self.continue_jumps {
+            out[pos] = Instructions::Jump(continue_target);
+        }
+        for pos in self.break_jumps {
+            out[pos] = Instructions::Jump(break_target);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod byte_code;
 pub mod comptime_variable_checker;
 pub mod instructions;
+pub mod loop_context;
 mod optimization;
 pub mod saving_bytes;
 pub mod functions_compiler_context;
+pub mod structs_compiler_context;
+pub mod assign_target;
+pub mod enums_compiler_context;
@@ -4,8 +4,8 @@ use std::collections::HashMap;
 /// Performs constant folding optimization and returns both the optimized code
 /// and a mapping from old instruction indices to new indices.
 ///
-/// This function combines adjacent constant operations (like PushNumber + PushNumber + Add)
-/// into single instructions while tracking how instruction positions change.
+/// This function combines adjacent constant operations (like PushNumber + PushNumber + Add
+/// or PushNumber + Negate) into single instructions while tracking how instruction positions change.
 ///
 /// # Returns
 /// A tuple of (optimized_code, old_to_new_mapping)
@@ -30,6 +30,15 @@ pub fn constant_folding(code: Vec<Instructions>) -> (Vec<Instructions>, HashMap<
                 i += 3;
             }
 
+            (Some(Instructions::PushNumber(a)), Some(Instructions::Negate), _) => {
+                // Negative literals like `-5` become a single push
+                old_to_new.insert(i, out.len());
+                old_to_new.insert(i + 1, out.len());
+
+                out.push(Instructions::PushNumber(-a));
+                i += 2;
+            }
+
             _ => {
                 old_to_new.insert(i, out.len());
                 out.push(code[i].clone());
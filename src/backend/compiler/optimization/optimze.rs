@@ -7,7 +7,7 @@ use std::collections::HashMap;
 ///
 /// This function applies various optimization passes to the instruction stream,
 /// such as constant folding. After each optimization, it ensures that all jump
-/// addresses (Jump, JumpIfFalse) are updated to point to the correct instructions
+/// addresses (Jump, JumpIfFalse, JumpIfTrue, Call, PushHandler, MakeClosure) are updated to point to the correct instructions
 /// in the optimized code.
 ///
 /// # Example
@@ -40,7 +40,7 @@ pub fn optimize(code: Vec<Instructions>) -> Vec<Instructions> {
 
 /// Fixes all jump addresses in the code using the provided index mapping.
 ///
-/// This function updates Jump and JumpIfFalse instructions to point to the correct
+/// This function updates Jump, JumpIfFalse, JumpIfTrue, Call, PushHandler and MakeClosure instructions to point to the correct
 /// positions after optimization has removed or combined instructions.
 ///
 /// # Arguments
@@ -79,6 +79,32 @@ fn fix_jump_addresses(
                 });
                 Instructions::JumpIfFalse(new_addr)
             }
+            Instructions::JumpIfTrue(old_addr) => {
+                let new_addr = old_to_new.get(&old_addr).copied().unwrap_or_else(|| {
+                    // If exact mapping doesn't exist, find the closest valid address
+                    find_closest_new_address(&old_to_new, old_addr)
+                });
+                Instructions::JumpIfTrue(new_addr)
+            }
+            Instructions::Call(old_addr, argc) => {
+                let new_addr = old_to_new.get(&old_addr).copied().unwrap_or_else(|| {
+                    // If exact mapping doesn't exist, find the closest valid address
+                    find_closest_new_address(&old_to_new, old_addr)
+                });
+                Instructions::Call(new_addr, argc)
+            }
+            Instructions::PushHandler(old_addr) => {
+                let new_addr = old_to_new.get(&old_addr).copied().unwrap_or_else(|| {
+                    find_closest_new_address(&old_to_new, old_addr)
+                });
+                Instructions::PushHandler(new_addr)
+            }
+            Instructions::MakeClosure(old_addr, captures) => {
+                let new_addr = old_to_new.get(&old_addr).copied().unwrap_or_else(|| {
+                    find_closest_new_address(&old_to_new, old_addr)
+                });
+                Instructions::MakeClosure(new_addr, captures)
+            }
             other => other,
         })
         .collect()
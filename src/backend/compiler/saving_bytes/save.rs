@@ -4,6 +4,7 @@ use crate::backend::{
         byte_code::{Compilable, Compiler},
         instructions::Instructions,
     },
+    errors::diagnostic::format_diagnostic,
     lexer::{tokenizer::Tokenizer, tokens::Token},
 };
 
@@ -46,10 +47,11 @@ pub fn build(dir: String, out: String, debug: bool) {
     /*
      * Lexer
      */
-    let mut main_lexer: Tokenizer = Tokenizer::new(fs::read_to_string(dir).unwrap());
+    let source = fs::read_to_string(&dir).unwrap();
+    let mut main_lexer: Tokenizer = Tokenizer::new(source.clone(), &dir);
     let tokens: &Vec<Token> = match main_lexer.tokenize() {
         Err(e) => {
-            print!("{}", e);
+            print!("{}", format_diagnostic(&e.to_string(), &source, e.span()));
             process::exit(-1);
         }
         Ok(tokens) => tokens,
@@ -59,7 +61,7 @@ pub fn build(dir: String, out: String, debug: bool) {
      */
     let mut main_parser: Parser = Parser::new(tokens.to_vec());
     let parsed_ast = main_parser.parse().unwrap_or_else(|e| {
-        println!("\x1b[1;31m{}\x1b[0m",e);
+        print!("{}", format_diagnostic(&e.to_string(), &source, e.span()));
         process::exit(-2)
     });
     /*
@@ -67,7 +69,7 @@ pub fn build(dir: String, out: String, debug: bool) {
      */
     let mut compiler = Compiler::new();
     if let Err(e) = parsed_ast.compile(&mut compiler) {
-        println!("\x1b[1;31m{}\x1b[0m", e);
+        print!("{}", format_diagnostic(&e.to_string(), &source, e.span()));
         println!("\x1b[1mTry:flarec error <error code> for fix\x1b[0m");
         process::exit(-3);
     }
@@ -79,7 +81,11 @@ pub fn build(dir: String, out: String, debug: bool) {
     }
 
     let out_path = format!("out/{}", out);
-    compile_to_exec(out_path, &mut compiler.out).expect("Cannot load binary file");
+    if let Err(e) = compile_to_exec(out_path.clone(), &mut compiler.out) {
+        println!("\x1b[1;31merror\x1b[0m\x1b[1m: {}\x1b[0m", e);
+        let _ = fs::remove_file(out_path);
+        process::exit(-4);
+    }
 
     // Calculate elapsed time and show success message
     let elapsed = start_time.elapsed();
@@ -88,6 +94,16 @@ pub fn build(dir: String, out: String, debug: bool) {
     println!("\x1b[1;32mFinished\x1b[0m in {:.3} seconds", seconds);
 }
 
+/// Checks that a count or index fits the single byte it is saved in.
+fn byte(value: usize, what: &str) -> std::io::Result<u8> {
+    u8::try_from(value).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Too many {} ({}), at most 255 are supported", what, value),
+        )
+    })
+}
+
 fn compile_to_exec(file_name: String, byte_code: &mut Vec<Instructions>) -> std::io::Result<()> {
     let file = File::create(file_name)?;
     let mut writer = BufWriter::new(file);
@@ -103,7 +119,7 @@ fn compile_to_exec(file_name: String, byte_code: &mut Vec<Instructions>) -> std:
                 writer.write_all(&[opcode])?;
                 let bytes = s.as_bytes();
                 writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
-                writer.write_all(&s.as_bytes())?
+                writer.write_all(s.as_bytes())?
             }
 
             //Values
@@ -134,13 +150,98 @@ fn compile_to_exec(file_name: String, byte_code: &mut Vec<Instructions>) -> std:
                 writer.write_all(&[opcode])?;
                 let bytes = v.as_bytes();
                 writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
-                writer.write_all(&v.as_bytes())?
+                writer.write_all(v.as_bytes())?
             }
             Instructions::SaveVar(v) => {
                 writer.write_all(&[opcode])?;
                 let bytes = v.as_bytes();
                 writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
-                writer.write_all(&v.as_bytes())?
+                writer.write_all(v.as_bytes())?
+            }
+            Instructions::LoadGlobal(v)
+            | Instructions::SaveGlobal(v)
+            | Instructions::LoadVarIndex(v)
+            | Instructions::StoreVarIndex(v)
+            | Instructions::PushVar(v)
+            | Instructions::LoadGlobalIndex(v)
+            | Instructions::StoreGlobalIndex(v)
+            | Instructions::PushGlobal(v) => {
+                writer.write_all(&[opcode])?;
+                let bytes = v.as_bytes();
+                writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                writer.write_all(v.as_bytes())?
+            }
+            Instructions::Call(adr, argc) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&(*adr as u16).to_le_bytes())?;
+                writer.write_all(&[byte(*argc, "call arguments")?])?;
+            }
+            Instructions::Return | Instructions::ReturnValue | Instructions::Pop => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::MakeStruct(name, count) => {
+                writer.write_all(&[opcode])?;
+                let bytes = name.as_bytes();
+                writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                writer.write_all(bytes)?;
+                writer.write_all(&[byte(*count, "struct fields")?])?;
+            }
+            Instructions::LoadField(idx) | Instructions::StoreField(idx) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&[byte(*idx, "fields")?])?;
+            }
+            Instructions::MakeTuple(count) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&[byte(*count, "tuple elements")?])?;
+            }
+            Instructions::MakeArray(count) | Instructions::MakeMap(count) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&(*count as u32).to_le_bytes())?;
+            }
+            Instructions::LoadIndex
+            | Instructions::StoreIndex
+            | Instructions::Len
+            | Instructions::ArrayPush
+            | Instructions::MapHas
+            | Instructions::MapRemove
+            | Instructions::MapKeys
+            | Instructions::PushUndef
+            | Instructions::PopHandler
+            | Instructions::Throw => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::MakeEnum(name, tag, count) => {
+                writer.write_all(&[opcode])?;
+                let bytes = name.as_bytes();
+                writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                writer.write_all(bytes)?;
+                writer.write_all(&[byte(*tag, "enum variants")?, byte(*count, "payload values")?])?;
+            }
+            Instructions::IsVariant(idx) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&[byte(*idx, "enum variants")?])?;
+            }
+            Instructions::LoadPayload(idx) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&[byte(*idx, "payload values")?])?;
+            }
+            Instructions::MakeClosure(adr, captures) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&(*adr as u16).to_le_bytes())?;
+                writer.write_all(&[byte(captures.len(), "captured variables")?])?;
+                for name in captures {
+                    let bytes = name.as_bytes();
+                    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                    writer.write_all(bytes)?;
+                }
+            }
+            Instructions::CallIndirect(argc) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&[byte(*argc, "call arguments")?])?;
+            }
+            Instructions::PushHandler(adr) => {
+                writer.write_all(&[opcode])?;
+                writer.write_all(&(*adr as u16).to_le_bytes())?;
             }
             Instructions::Jump(adr) => {
                 writer.write_all(&[opcode])?;
@@ -159,9 +260,27 @@ fn compile_to_exec(file_name: String, byte_code: &mut Vec<Instructions>) -> std:
             Instructions::Equal => {
                 writer.write_all(&[opcode])?;
             }
+            Instructions::NotEqual => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::LessEqual => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::GreaterEqual => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::Not => {
+                writer.write_all(&[opcode])?;
+            }
+            Instructions::Negate => {
+                writer.write_all(&[opcode])?;
+            }
             Instructions::ReadInput => {
                 writer.write_all(&[opcode])?;
             }
+            Instructions::ToString => {
+                writer.write_all(&[opcode])?;
+            }
 
             Instructions::Halt => writer.write_all(&[opcode])?,
         }
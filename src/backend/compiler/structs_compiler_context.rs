@@ -0,0 +1,11 @@
+use crate::backend::ast::statements::structs::StructVariable;
+#[derive(Clone)]
+pub struct CompileTimeStructForCheck{
+    pub fields:Vec<StructVariable>
+}
+impl CompileTimeStructForCheck {
+    /// Position of a field in the runtime struct and its definition.
+    pub fn field(&self, name: &str) -> Option<(usize, &StructVariable)> {
+        self.fields.iter().enumerate().find(|(_, field)| field.name == name)
+    }
+}
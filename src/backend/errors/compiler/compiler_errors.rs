@@ -1,49 +1,239 @@
 use crate::backend::compiler::comptime_variable_checker::comptime_value_for_check::ComptimeValueType;
+use crate::backend::lexer::tokens::Span;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 
 pub enum CompileError {
     #[error("[E0001]Unknown macro: {name}")]
-    UnknownMacro { name: String },
+    UnknownMacro { name: String, span: Span },
 
     #[error("[E0002]Cannot infer type for {name}")]
-    CannotInferType { name: String },
+    CannotInferType { name: String, span: Span },
 
     #[error("[E0003]Undefined type: {undefined_type}")]
-    UndefinedType { undefined_type: String },
+    UndefinedType { undefined_type: String, span: Span },
 
     #[error("[E0004]Type mismatch: expected {expected:?}, found {found:?}")]
     TypeMismatch {
         expected: ComptimeValueType,
         found: ComptimeValueType,
+        span: Span,
     },
 
     #[error("[E0005]Invalid binary operation: {op} between {left:?} and {right:?}")]
     InvalidBinaryOp {
         op: &'static str,
-        left: ComptimeValueType,
-        right: ComptimeValueType,
+        left: Box<ComptimeValueType>,
+        right: Box<ComptimeValueType>,
+        span: Span,
     },
 
     #[error("[E0006]Undefined variable: {name}")]
-    UndefinedVariable { name: String },
+    UndefinedVariable { name: String, span: Span },
 
     #[error("[E0007]Variable {name} already exists")]
-    VariableRecreation { name: String },
+    VariableRecreation { name: String, span: Span },
 
     #[error("[E0008]Cannot have constant without value")]
-    ConstantWithoutValue { name: String },
+    ConstantWithoutValue { name: String, span: Span },
     #[error("[E0009]Cannot reassign constant {name}")]
-    ConstReassignment { name: String },
+    ConstReassignment { name: String, span: Span },
     #[error("[E0010]Wrong macro argument count: expected {expected}, found {found}")]
-    WrongMacroArgCount { expected: usize, found: usize },
+    WrongMacroArgCount {
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
     #[error("[E0011]Expected printable but found {found:?}")]
-    ExpectedPrintable { found: ComptimeValueType },
+    ExpectedPrintable { found: ComptimeValueType, span: Span },
     #[error("[E0012]Function {name} is already defined")]
-    FunctionAlredyExists{name:String},
+    FunctionAlredyExists { name: String, span: Span },
     #[error("[E0013]Unknown function:{name}")]
-    UnknownFunction{name:String},
-    #[error("[E0014]Unexpected number of arguments at function {name}: expected {expected} but got {found}")]
-    UnexpectedFunctionArguments{name:String,expected:usize,found:usize}
+    UnknownFunction { name: String, span: Span },
+    #[error(
+        "[E0014]Unexpected number of arguments at function {name}: expected {expected} but got {found}"
+    )]
+    UnexpectedFunctionArguments {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    #[error("[E0015]`{keyword}` outside of a loop")]
+    OutsideOfLoop { keyword: &'static str, span: Span },
+    #[error("[E0016]Undefined loop label: '{label}")]
+    UndefinedLabel { label: String, span: Span },
+    #[error("[E0017]Non-exhaustive match: {missing} not covered")]
+    NonExhaustiveMatch { missing: String, span: Span },
+    #[error("[E0018]`return` outside of a function")]
+    ReturnOutsideFunction { span: Span },
+    #[error("[E0019]Function {name} does not return a value on every path")]
+    MissingReturn { name: String, span: Span },
+    #[error("[E0020]{function} has no parameter named {argument}")]
+    UnknownArgument {
+        function: String,
+        argument: String,
+        span: Span,
+    },
+    #[error("[E0021]Argument {argument} is given more than once")]
+    DuplicateArgument { argument: String, span: Span },
+    #[error("[E0022]Missing argument {argument} in call to {function}")]
+    MissingArgument {
+        function: String,
+        argument: String,
+        span: Span,
+    },
+    #[error("[E0023]Type {name} is already defined")]
+    TypeAlreadyExists { name: String, span: Span },
+    #[error("[E0024]{type_name} has no field {field}")]
+    UnknownField {
+        type_name: String,
+        field: String,
+        span: Span,
+    },
+    #[error("[E0025]Missing field {field} in {struct_name}")]
+    MissingField {
+        struct_name: String,
+        field: String,
+        span: Span,
+    },
+    #[error("[E0026]Field {field} is given more than once")]
+    DuplicateField { field: String, span: Span },
+    #[error("[E0027]{type_name} has no method {name}")]
+    UnknownMethod {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+    #[error("[E0028]{type_name}.{name} takes self and must be called on a value")]
+    MethodCalledOnType {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+    #[error("[E0029]{type_name}.{name} doesn't take self and must be called on the type")]
+    StaticCalledOnValue {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+    #[error("[E0030]Cannot index into {found:?}")]
+    NotIndexable { found: ComptimeValueType, span: Span },
+    #[error("[E0031]Cannot assign to this expression")]
+    NotAssignable { span: Span },
+    #[error("[E0032]{enum_name} has no variant {variant}")]
+    UnknownVariant {
+        enum_name: String,
+        variant: String,
+        span: Span,
+    },
+    #[error("[E0033]{variant} holds {expected} values, found {found}")]
+    VariantPayloadCount {
+        variant: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    #[error("[E0034]Variant {variant} is defined more than once")]
+    DuplicateVariant { variant: String, span: Span },
+    #[error("[E0035]{found:?} cannot be used as a map key")]
+    InvalidMapKey { found: ComptimeValueType, span: Span },
+    #[error("[E0036]Expected a map but found {found:?}")]
+    ExpectedMap { found: ComptimeValueType, span: Span },
+    #[error("[E0037]Cannot take {found:?} apart into {pattern}")]
+    PatternMismatch {
+        pattern: String,
+        found: ComptimeValueType,
+        span: Span,
+    },
+    #[error("[E0038]{found:?} may be undef, check it with `!= undef` or use unwrapOr! first")]
+    UncheckedOptional { found: ComptimeValueType, span: Span },
+    #[error("[E0039]Variables of type {found:?} have no default value and need one")]
+    NoDefaultValue { found: ComptimeValueType, span: Span },
+    #[error("[E0040]{found:?} cannot be called")]
+    NotCallable { found: ComptimeValueType, span: Span },
+    #[error("[E0041]Unknown constraint {constraint}, expected numeric or printable")]
+    UnknownConstraint { constraint: String, span: Span },
+    #[error("[E0042]{found:?} does not satisfy the constraint {constraint} of {name}")]
+    UnsatisfiedConstraint {
+        name: String,
+        constraint: String,
+        found: Box<ComptimeValueType>,
+        span: Span,
+    },
+    #[error("[E0043]Cannot infer the type parameter {name} of {function} from the arguments")]
+    CannotInferTypeParameter {
+        name: String,
+        function: String,
+        span: Span,
+    },
+    #[error("[E0044]The step of a for loop must be greater than 0")]
+    InvalidStep { span: Span },
+    #[error("[E0045]Cannot assign to self, it is a copy of the value the method is called on")]
+    SelfAssignment { span: Span },
+}
+
+impl CompileError {
+    /// Error for assigning to the constant `name`, `self` gets its own message.
+    pub fn reassignment(name: &str, span: &Span) -> CompileError {
+        if name == "self" {
+            CompileError::SelfAssignment { span: span.clone() }
+        } else {
+            CompileError::ConstReassignment {
+                name: name.to_string(),
+                span: span.clone(),
+            }
+        }
+    }
+
+    pub fn span(&self) -> &Span {
+        match self {
+            CompileError::UnknownMacro { span, .. }
+            | CompileError::CannotInferType { span, .. }
+            | CompileError::UndefinedType { span, .. }
+            | CompileError::TypeMismatch { span, .. }
+            | CompileError::InvalidBinaryOp { span, .. }
+            | CompileError::UndefinedVariable { span, .. }
+            | CompileError::VariableRecreation { span, .. }
+            | CompileError::ConstantWithoutValue { span, .. }
+            | CompileError::ConstReassignment { span, .. }
+            | CompileError::WrongMacroArgCount { span, .. }
+            | CompileError::ExpectedPrintable { span, .. }
+            | CompileError::FunctionAlredyExists { span, .. }
+            | CompileError::UnknownFunction { span, .. }
+            | CompileError::UnexpectedFunctionArguments { span, .. }
+            | CompileError::OutsideOfLoop { span, .. }
+            | CompileError::UndefinedLabel { span, .. }
+            | CompileError::NonExhaustiveMatch { span, .. }
+            | CompileError::ReturnOutsideFunction { span }
+            | CompileError::MissingReturn { span, .. }
+            | CompileError::UnknownArgument { span, .. }
+            | CompileError::DuplicateArgument { span, .. }
+            | CompileError::MissingArgument { span, .. }
+            | CompileError::TypeAlreadyExists { span, .. }
+            | CompileError::UnknownField { span, .. }
+            | CompileError::MissingField { span, .. }
+            | CompileError::DuplicateField { span, .. }
+            | CompileError::UnknownMethod { span, .. }
+            | CompileError::MethodCalledOnType { span, .. }
+            | CompileError::StaticCalledOnValue { span, .. }
+            | CompileError::NotIndexable { span, .. }
+            | CompileError::NotAssignable { span }
+            | CompileError::UnknownVariant { span, .. }
+            | CompileError::VariantPayloadCount { span, .. }
+            | CompileError::DuplicateVariant { span, .. }
+            | CompileError::InvalidMapKey { span, .. }
+            | CompileError::ExpectedMap { span, .. }
+            | CompileError::PatternMismatch { span, .. }
+            | CompileError::UncheckedOptional { span, .. }
+            | CompileError::NoDefaultValue { span, .. }
+            | CompileError::NotCallable { span, .. }
+            | CompileError::UnknownConstraint { span, .. }
+            | CompileError::UnsatisfiedConstraint { span, .. }
+            | CompileError::CannotInferTypeParameter { span, .. }
+            | CompileError::InvalidStep { span }
+            | CompileError::SelfAssignment { span } => span,
+        }
+    }
 }
@@ -109,14 +109,332 @@ pub static ERROR_EXPLAIN: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(
     m.insert(
         "E0011",
         "Expected printable but found ... \n\
-        Compiler expects something that can be printed; something like number and string but bool for example isnt printable\n\
+        Compiler expects something that can be printed; numbers, strings and bools are printable but arrays for example arent\n\
         Example:\n\
         ```
-        const b:bool = true;
-        writeLn!(b)
+        const a = [1, 2];
+        writeLn!(a)
         ```
 
 
         ");
+    m.insert(
+        "E0015",
+        "`break` or `continue` used outside of a loop.\n\
+         Example:\n\
+         ```\n\
+         if (true) {\n\
+             break;\n\
+         }\n\
+         ```\n\
+         Fix: Only use `break` and `continue` inside `loop`, `while` or `for` bodies.",
+    );
+    m.insert(
+        "E0016",
+        "Undefined loop label.\n\
+         Example:\n\
+         ```\n\
+         'outer: loop {\n\
+             break 'inner;\n\
+         }\n\
+         ```\n\
+         Fix: Use the label of a loop that encloses the `break`/`continue`.",
+    );
+    m.insert(
+        "E0017",
+        "Non-exhaustive match.\n\
+         A match over a bool has to handle both `true` and `false`.\n\
+         Example:\n\
+         ```\n\
+         match flag {\n\
+             true => writeLn!(\"yes\"),\n\
+         }\n\
+         ```\n\
+         Fix: Add the missing arm or a wildcard arm `_ => ...`.",
+    );
+    m.insert(
+        "E0018",
+        "`return` used outside of a function.\n\
+         Example:\n\
+         ```\n\
+         var x = 5;\n\
+         return x;\n\
+         ```\n\
+         Fix: Only use `return` inside a function body.",
+    );
+    m.insert(
+        "E0019",
+        "Function with a return type can end without returning.\n\
+         Example:\n\
+         ```\n\
+         fnc sign(x: numb): numb {\n\
+             if (x > 0) {\n\
+                 return 1;\n\
+             }\n\
+         }\n\
+         ```\n\
+         Fix: Return a value on every path, for example with an `else` branch or a final `return`.",
+    );
+    m.insert(
+        "E0020",
+        "Named argument doesn't match any parameter.\n\
+         Example:\n\
+         ```\n\
+         fnc greet(name: string) {}\n\
+         greet(nmae = \"Bob\")\n\
+         ```\n\
+         Fix: Use one of the parameter names from the function definition.",
+    );
+    m.insert(
+        "E0021",
+        "Argument given more than once.\n\
+         Example:\n\
+         ```\n\
+         fnc greet(name: string) {}\n\
+         greet(\"Bob\", name = \"Al\")\n\
+         ```\n\
+         Fix: Pass every argument either by position or by name, but only once.",
+    );
+    m.insert(
+        "E0022",
+        "Missing argument.\n\
+         Example:\n\
+         ```\n\
+         fnc greet(name: string, greeting: string = \"Hello\") {}\n\
+         greet(greeting = \"Hi\")\n\
+         ```\n\
+         Fix: Pass a value for every parameter without a default value.",
+    );
+    m.insert(
+        "E0023",
+        "Struct or enum name defined more than once.\n\
+         Example:\n\
+         ```\n\
+         struct Point { x: flt }\n\
+         enum Point { Origin }\n\
+         ```\n\
+         Fix: Rename one of the types.",
+    );
+    m.insert(
+        "E0024",
+        "Field doesn't exist.\n\
+         Example:\n\
+         ```\n\
+         struct Point { x: flt, y: flt }\n\
+         var p = Point { x: 1.0, y: 2.0 };\n\
+         writeLn!(p.z)\n\
+         ```\n\
+         Fix: Use one of the fields from the struct definition. Only structs have fields.",
+    );
+    m.insert(
+        "E0025",
+        "Struct literal is missing a field.\n\
+         Example:\n\
+         ```\n\
+         struct Point { x: flt, y: flt }\n\
+         var p = Point { x: 1.0 };\n\
+         ```\n\
+         Fix: Give a value to every field of the struct.",
+    );
+    m.insert(
+        "E0026",
+        "Field given more than once.\n\
+         Example:\n\
+         ```\n\
+         var p = Point { x: 1.0, x: 2.0 };\n\
+         ```\n\
+         Fix: Remove the repeated field.",
+    );
+    m.insert(
+        "E0027",
+        "Method doesn't exist.\n\
+         Example:\n\
+         ```\n\
+         struct Point { x: flt }\n\
+         var p = Point { x: 1.0 };\n\
+         p.length()\n\
+         ```\n\
+         Fix: Define the method in an `impl Point { }` block or fix its name.",
+    );
+    m.insert(
+        "E0028",
+        "Method called on the type.\n\
+         Example:\n\
+         ```\n\
+         impl Point { fnc length(self): flt { return self.x; } }\n\
+         Point.length()\n\
+         ```\n\
+         Fix: Call the method on a value, for example `p.length()`.",
+    );
+    m.insert(
+        "E0029",
+        "Associated function called on a value.\n\
+         Example:\n\
+         ```\n\
+         impl Point { fnc new(x: flt): Point { return Point { x: x }; } }\n\
+         p.new(1.0)\n\
+         ```\n\
+         Fix: Call it on the type instead, for example `Point.new(1.0)`.",
+    );
+    m.insert(
+        "E0030",
+        "Indexing a value that isn't an array.\n\
+         Example:\n\
+         ```\n\
+         var n = 5;\n\
+         writeLn!(n[0])\n\
+         ```\n\
+         Fix: Only index arrays, like `numbers[0]`.",
+    );
+    m.insert(
+        "E0031",
+        "Assigning to something that isn't a variable, field or element.\n\
+         Example:\n\
+         ```\n\
+         make_point().x = 1.0;\n\
+         push!([1, 2], 3)\n\
+         ```\n\
+         Fix: Store the value in a variable first and assign to that.",
+    );
+    m.insert(
+        "E0032",
+        "Using a variant the enum doesn't define.\n\
+         Example:\n\
+         ```\n\
+         enum Shape { Circle(flt), Empty }\n\
+         var s = Shape.Square(2.0);\n\
+         ```\n\
+         Fix: Use one of the variants listed in the enum definition.",
+    );
+    m.insert(
+        "E0033",
+        "Building or matching a variant with the wrong number of values.\n\
+         Example:\n\
+         ```\n\
+         enum Shape { Rect(flt, flt) }\n\
+         var s = Shape.Rect(2.0);\n\
+         ```\n\
+         Fix: Give one value or binding for every type in the variant.",
+    );
+    m.insert(
+        "E0034",
+        "Enum with two variants of the same name.\n\
+         Example:\n\
+         ```\n\
+         enum Shape { Circle(flt), Circle(numb) }\n\
+         ```\n\
+         Fix: Rename one of the variants.",
+    );
+    m.insert(
+        "E0035",
+        "Map key of a type other than string or numb.\n\
+         Example:\n\
+         ```\n\
+         var m: map<flt, string>;\n\
+         ```\n\
+         Fix: Use string or numb keys, like `map<numb, string>`.",
+    );
+    m.insert(
+        "E0036",
+        "Passing something other than a map to a map macro.\n\
+         Example:\n\
+         ```\n\
+         var names = [\"a\"];\n\
+         has!(names, \"a\")\n\
+         ```\n\
+         Fix: Pass a map to `has!`, `remove!` and `keys!`.",
+    );
+    m.insert(
+        "E0037",
+        "Destructuring a value that isn't a tuple of the same length.\n\
+         Example:\n\
+         ```\n\
+         var (a, b) = (1, 2, 3);\n\
+         ```\n\
+         Fix: Use one name for every element of the tuple, `_` skips one.",
+    );
+    m.insert(
+        "E0038",
+        "Using an optional value that may be undef.\n\
+         Example:\n\
+         ```\n\
+         var x: numb? = undef;\n\
+         writeLn!(x + 1)\n\
+         ```\n\
+         Fix: Check it first with `if (x != undef) { ... }`, or use `unwrapOr!(x, 0)`.\n\
+         Top level variables stay optional inside the check when it calls or defines a function,\n\
+         which could set them back to undef. Use `unwrapOr!` or do the work in a function.",
+    );
+    m.insert(
+        "E0039",
+        "Declaring a variable without a value. Only optionals, arrays and maps have one to start with,\n\
+         `undef` or empty.\n\
+         Example:\n\
+         ```\n\
+         var count: numb;\n\
+         ```\n\
+         Fix: Give it a value, like `var count: numb = 0;`, or make it optional with `numb?`.",
+    );
+    m.insert(
+        "E0040",
+        "Calling a value that isn't a function.\n\
+         Example:\n\
+         ```\n\
+         var x = 5;\n\
+         writeLn!((x)(1))\n\
+         ```\n\
+         Fix: Only values of a `fnc(...)` type can be called.",
+    );
+    m.insert(
+        "E0041",
+        "Constraining a type parameter with something that isn't a constraint.\n\
+         Example:\n\
+         ```\n\
+         fnc show<T: text>(value: T) { }\n\
+         ```\n\
+         Fix: Use `numeric` for `numb` and `flt`, or `printable` for `numb`, `flt`, `string` and `bool`,\n\
+         which is what `writeLn!` takes.",
+    );
+    m.insert(
+        "E0042",
+        "Calling a generic function with a type its constraint doesn't allow.\n\
+         Example:\n\
+         ```\n\
+         fnc max<T: numeric>(a: T, b: T): T { if (a > b) { return a; } return b; }\n\
+         max(\"a\", \"b\")\n\
+         ```\n\
+         Fix: Pass values of a type that meets the constraint.",
+    );
+    m.insert(
+        "E0043",
+        "Calling a generic function whose type parameter isn't used by any argument.\n\
+         Example:\n\
+         ```\n\
+         fnc empty<T>(): T[] { return []; }\n\
+         var a = empty();\n\
+         ```\n\
+         Fix: Use every type parameter in the type of an argument.",
+    );
+    m.insert(
+        "E0044",
+        "Using a step of 0 or less in a for loop, which would never reach the end.\n\
+         Example:\n\
+         ```\n\
+         for i in 0..10 step 0 { }\n\
+         ```\n\
+         Fix: Use a step greater than 0. Steps that are only known at runtime are checked when the loop starts.",
+    );
+
+    m.insert(
+        "E0045",
+        "Assigning to self or one of its fields inside a method.\n\
+         Example:\n\
+         ```\n\
+         impl Counter {\n\
+             fnc increment(self) { self.count = self.count + 1; }\n\
+         }\n\
+         ```\n\
+         Fix: self is a copy of the value the method is called on, return the changed value instead.",
+    );
     m
 });
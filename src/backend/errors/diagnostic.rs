@@ -0,0 +1,53 @@
+use crate::backend::lexer::tokens::Span;
+
+/// Formats an error message followed by a rustc-style snippet of the source
+/// with a caret under the code described by `span`.
+///
+/// ```text
+/// error: [E0004]Type mismatch: expected Int, found Float
+///   --> main.flare:1:14
+///    |
+///  1 | var x:numb = 3.5;
+///    |              ^^^
+/// ```
+pub fn format_diagnostic(message: &str, source: &str, span: &Span) -> String {
+    let mut out = format!("\x1b[1;31merror\x1b[0m\x1b[1m: {}\x1b[0m\n", message);
+    out.push_str(&render_snippet(source, span));
+    out
+}
+
+/// Renders the source line of `span` with carets under the spanned code.
+pub fn render_snippet(source: &str, span: &Span) -> String {
+    let line_number = span.line.max(1);
+    let gutter = " ".repeat(line_number.to_string().len());
+    let mut out = format!(
+        "{}\x1b[1;34m-->\x1b[0m {}:{}:{}\n",
+        gutter, span.file, line_number, span.column
+    );
+
+    let Some(line) = source.lines().nth(line_number - 1) else {
+        return out;
+    };
+    let prefix: String = line
+        .chars()
+        .take(span.column.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let remaining = line.chars().count().saturating_sub(prefix.chars().count());
+    let spanned = source
+        .get(span.start..span.end.max(span.start))
+        .map(|text| text.chars().take_while(|c| *c != '\n').count())
+        .unwrap_or(0);
+    let carets = "^".repeat(spanned.min(remaining).max(1));
+
+    out.push_str(&format!("{} \x1b[1;34m|\x1b[0m\n", gutter));
+    out.push_str(&format!(
+        "\x1b[1;34m{} |\x1b[0m {}\n",
+        line_number, line
+    ));
+    out.push_str(&format!(
+        "{} \x1b[1;34m|\x1b[0m {}\x1b[1;31m{}\x1b[0m\n",
+        gutter, prefix, carets
+    ));
+    out
+}
@@ -1,12 +1,35 @@
 use thiserror::Error;
+
+use crate::backend::lexer::tokens::Span;
+
 #[derive(Debug, Error)]
 pub enum LexerError {
     #[error("Unknown token: {wrong_token}")]
-    UnknownToken { wrong_token: String },
+    UnknownToken { wrong_token: String, span: Span },
     #[error("Cannot have more dots in a number")]
-    MoreDotInANumber,
+    MoreDotInANumber { span: Span },
     #[error("Unterminated string:{text}")]
-    UnterminatedString{text:String},
+    UnterminatedString { text: String, span: Span },
     #[error("Cannot parse empty file")]
-    EmptyFile,
+    EmptyFile { span: Span },
+    #[error("Unterminated block comment")]
+    UnterminatedComment { span: Span },
+    #[error("Invalid escape sequence in string: {sequence}")]
+    InvalidEscape { sequence: String, span: Span },
+    #[error("Empty interpolation in string, use \\{{ for a literal brace")]
+    EmptyInterpolation { span: Span },
+}
+
+impl LexerError {
+    pub fn span(&self) -> &Span {
+        match self {
+            LexerError::UnknownToken { span, .. }
+            | LexerError::MoreDotInANumber { span }
+            | LexerError::UnterminatedString { span, .. }
+            | LexerError::EmptyFile { span }
+            | LexerError::UnterminatedComment { span }
+            | LexerError::InvalidEscape { span, .. }
+            | LexerError::EmptyInterpolation { span } => span,
+        }
+    }
 }
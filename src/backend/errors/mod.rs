@@ -1,5 +1,6 @@
 pub mod cli_errors;
 pub mod compiler;
+pub mod diagnostic;
 pub mod lexer_errors;
 pub mod parser_errors;
 pub mod runtime_error;
@@ -1,12 +1,25 @@
 use thiserror::Error;
 
-use crate::backend::lexer::tokens::TokenKind;
+use crate::backend::lexer::tokens::{Span, TokenKind};
 
-#[derive(Debug,Error)]
+#[derive(Debug, Error)]
 pub enum ParserError {
     #[error("Syntax error:expected{expected:?}but found{found}")]
-    UnexpectedToken { found: String, expected: TokenKind },
+    UnexpectedToken {
+        found: String,
+        expected: TokenKind,
+        span: Span,
+    },
     #[error("Syntax error:expected type but found{found}")]
-    ExpectedType{found:String}
-    
+    ExpectedType { found: String, span: Span },
+}
+
+impl ParserError {
+    pub fn span(&self) -> &Span {
+        match self {
+            ParserError::UnexpectedToken { span, .. } | ParserError::ExpectedType { span, .. } => {
+                span
+            }
+        }
+    }
 }
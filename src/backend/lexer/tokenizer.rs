@@ -1,14 +1,14 @@
-use std::error::Error;
+use std::sync::Arc;
 
 use crate::backend::lexer::tokens::TokenKind::{COMMA, FALSE, SEMICOLON, TRUE};
 use crate::{
     backend::errors::lexer_errors::LexerError,
     backend::lexer::tokens::{
-        Token, TokenKind,
+        Span, Token, TokenKind,
         TokenKind::{
-            CLOSINGBRACE, COLON, CONST, DIVIDE, ELSE, EOF, EQUAL, FLOAT, FNC, IDENTIFIER, IF,
+            AS, CLOSINGBRACE, COLON, CONST, DIVIDE, ELSE, EOF, EQUAL, FLOAT, FNC, IDENTIFIER, IF,
             LEFTPAREN, LOOP, MINUS, MODULO, NUMB, OPENINGBRACE, PLUS, RIGHTPAREN, STR, TIMES, VAR,
-            WHILE,AS
+            WHILE,
         },
     },
 };
@@ -19,96 +19,118 @@ pub struct Tokenizer {
     token_count: usize,
     source_text: Vec<char>,
     final_tokens: Vec<Token>,
+    file: Arc<str>,
+    line: usize,
+    column: usize,
+    byte_pos: usize,
+    token_start: (usize, usize, usize),
+    pending_doc: Vec<String>,
 }
 
 impl Tokenizer {
-    pub fn new(text: String) -> Self {
+    pub fn new(text: String, file: &str) -> Self {
+        let source_text: Vec<char> = text.chars().collect();
         Self {
             token_idx: 0,
-            token_count: text.len(),
+            token_count: source_text.len(),
             current_token: '0',
-            source_text: text.chars().collect(),
+            source_text,
             final_tokens: Vec::new(),
+            file: Arc::from(file),
+            line: 1,
+            column: 1,
+            byte_pos: 0,
+            token_start: (1, 1, 0),
+            pending_doc: Vec::new(),
         }
     }
-    pub fn tokenize(&mut self) -> Result<&Vec<Token>, Box<dyn Error>> {
+    pub fn tokenize(&mut self) -> Result<&Vec<Token>, LexerError> {
         if self.source_text.is_empty() {
-            return Err(LexerError::EmptyFile.into());
+            return Err(LexerError::EmptyFile {
+                span: self.current_span(),
+            });
         }
         self.current_token = self.source_text[0];
         while self.current_token != '\0' {
-            match self.current_token {
+            self.mark_token_start();
+            let token_kind = match self.current_token {
                 ' ' | '\n' | '\t' | '\r' => {
                     self.advance();
                     continue;
                 }
                 '"' => {
-                    let token = self.read_string()?;
+                    self.read_string()?;
+                    continue;
+                }
+                'r' if self.peek_char() == '"' || self.peek_char() == '#' => {
+                    let token = self.read_raw_string()?;
                     self.final_tokens.push(token);
                     continue;
                 }
-                ':' => self.final_tokens.push(Token {
-                    token_kind: COLON,
-                    token_value: self.current_token.to_string(),
-                }),
-                '+' => self.final_tokens.push(Token {
-                    token_kind: PLUS,
-                    token_value: self.current_token.to_string(),
-                }),
-                ',' => self.final_tokens.push(Token {
-                    token_kind: COMMA,
-                    token_value: self.current_token.to_string(),
-                }),
-                ';' => self.final_tokens.push(Token {
-                    token_kind: SEMICOLON,
-                    token_value: self.current_token.to_string(),
-                }),
-                '=' => self.final_tokens.push(Token {
-                    token_kind: EQUAL,
-                    token_value: self.current_token.to_string(),
-                }),
-                '(' => self.final_tokens.push(Token {
-                    token_kind: LEFTPAREN,
-                    token_value: self.current_token.to_string(),
-                }),
-                ')' => self.final_tokens.push(Token {
-                    token_kind: RIGHTPAREN,
-                    token_value: self.current_token.to_string(),
-                }),
-                '{' => self.final_tokens.push(Token {
-                    token_kind: OPENINGBRACE,
-                    token_value: self.current_token.to_string(),
-                }),
-                '}' => self.final_tokens.push(Token {
-                    token_kind: CLOSINGBRACE,
-                    token_value: self.current_token.to_string(),
-                }),
-                '-' => self.final_tokens.push(Token {
-                    token_kind: MINUS,
-                    token_value: self.current_token.to_string(),
-                }),
-                '*' => self.final_tokens.push(Token {
-                    token_kind: TIMES,
-                    token_value: self.current_token.to_string(),
-                }),
-                '/' => self.final_tokens.push(Token {
-                    token_kind: DIVIDE,
-                    token_value: self.current_token.to_string(),
-                }),
-                '%' => self.final_tokens.push(Token {
-                    token_kind: MODULO,
-                    token_value: self.current_token.to_string(),
-                }),
-                '>' => self.final_tokens.push(Token {
-                    token_kind: TokenKind::GREATER,
-                    token_value: self.current_token.to_string(),
-                }),
-                '<' => self.final_tokens.push(Token {
-                    token_kind: TokenKind::LESS,
-                    token_value: self.current_token.to_string(),
-                }),
+                '/' if self.peek_char() == '/' => {
+                    self.skip_line_comment();
+                    continue;
+                }
+                '/' if self.peek_char() == '*' => {
+                    self.skip_block_comment()?;
+                    continue;
+                }
+                '=' | '!' | '<' | '>' | '&' | '|' if self.is_double_operator() => {
+                    let mut token_value = self.current_token.to_string();
+                    self.advance();
+                    token_value.push(self.current_token);
+                    self.advance();
+                    let token_kind = match token_value.as_str() {
+                        "==" => TokenKind::EQUALEQUAL,
+                        "!=" => TokenKind::NOTEQUAL,
+                        "<=" => TokenKind::LESSEQUAL,
+                        ">=" => TokenKind::GREATEREQUAL,
+                        "&&" => TokenKind::AND,
+                        "=>" => TokenKind::FATARROW,
+                        _ => TokenKind::OR,
+                    };
+                    self.push_token(token_kind, token_value);
+                    continue;
+                }
+                '\'' if self.peek_char().is_alphabetic() => {
+                    let token = self.read_label();
+                    self.final_tokens.push(token);
+                    continue;
+                }
+                '.' if self.peek_char() == '.' => {
+                    self.advance();
+                    self.advance();
+                    if self.current_token == '=' {
+                        self.advance();
+                        self.push_token(TokenKind::DOTDOTEQUAL, "..=".to_string());
+                    } else {
+                        self.push_token(TokenKind::DOTDOT, "..".to_string());
+                    }
+                    continue;
+                }
+                '.' => TokenKind::DOT,
+                '!' => TokenKind::NOT,
+                ':' => COLON,
+                '?' => TokenKind::QUESTION,
+                '|' => TokenKind::PIPE,
+                '+' => PLUS,
+                ',' => COMMA,
+                ';' => SEMICOLON,
+                '=' => EQUAL,
+                '(' => LEFTPAREN,
+                ')' => RIGHTPAREN,
+                '{' => OPENINGBRACE,
+                '}' => CLOSINGBRACE,
+                '[' => TokenKind::LEFTBRACKET,
+                ']' => TokenKind::RIGHTBRACKET,
+                '-' => MINUS,
+                '*' => TIMES,
+                '/' => DIVIDE,
+                '%' => MODULO,
+                '>' => TokenKind::GREATER,
+                '<' => TokenKind::LESS,
                 _ => {
-                    if self.current_token.is_alphabetic() {
+                    if self.current_token.is_alphabetic() || self.current_token == '_' {
                         let token = self.create_text_token();
                         self.final_tokens.push(token);
                         continue;
@@ -117,22 +139,33 @@ impl Tokenizer {
                         self.final_tokens.push(token);
                         continue;
                     } else {
+                        let wrong_token = self.current_token.to_string();
+                        self.advance();
                         return Err(LexerError::UnknownToken {
-                            wrong_token: self.current_token.to_string(),
-                        }
-                        .into());
+                            wrong_token,
+                            span: self.token_span(),
+                        });
                     }
                 }
-            }
+            };
+            let token_value = self.current_token.to_string();
             self.advance();
+            self.push_token(token_kind, token_value);
         }
-        self.final_tokens.push(Token {
-            token_kind: EOF,
-            token_value: "EOF".to_string(),
-        });
+        self.mark_token_start();
+        self.push_token(EOF, "EOF".to_string());
         Ok(&self.final_tokens)
     }
     fn advance(&mut self) {
+        if self.token_idx < self.token_count {
+            if self.current_token == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+            self.byte_pos += self.current_token.len_utf8();
+        }
         self.token_idx += 1;
         if self.token_idx >= self.token_count {
             self.current_token = '\0';
@@ -140,118 +173,387 @@ impl Tokenizer {
             self.current_token = self.source_text[self.token_idx];
         }
     }
+    fn mark_token_start(&mut self) {
+        self.token_start = (self.line, self.column, self.byte_pos);
+    }
+    /// Span from the last `mark_token_start` up to the current position.
+    fn token_span(&self) -> Span {
+        let (line, column, start) = self.token_start;
+        Span {
+            file: self.file.clone(),
+            line,
+            column,
+            start,
+            end: self.byte_pos,
+        }
+    }
+    fn current_span(&self) -> Span {
+        Span {
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column,
+            start: self.byte_pos,
+            end: self.byte_pos + self.current_token.len_utf8(),
+        }
+    }
+    fn peek_char(&self) -> char {
+        self.peek_nth(1)
+    }
+    fn peek_nth(&self, n: usize) -> char {
+        self.source_text
+            .get(self.token_idx + n)
+            .copied()
+            .unwrap_or('\0')
+    }
+    fn push_token(&mut self, token_kind: TokenKind, token_value: String) {
+        let token = self.make_token(token_kind, token_value);
+        self.final_tokens.push(token);
+    }
+    fn make_token(&mut self, token_kind: TokenKind, token_value: String) -> Token {
+        let doc_comment = if self.pending_doc.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.pending_doc).join("\n"))
+        };
+        Token {
+            token_kind,
+            token_value,
+            span: self.token_span(),
+            doc_comment,
+        }
+    }
+    /// Skips a `//` comment. `///` comments are kept as documentation for
+    /// the next token, `////` and longer are ordinary comments.
+    fn skip_line_comment(&mut self) {
+        let mut text = String::new();
+        while self.current_token != '\n' && self.current_token != '\0' {
+            text.push(self.current_token);
+            self.advance();
+        }
+        if let Some(doc) = text.strip_prefix("///")
+            && !doc.starts_with('/')
+        {
+            let doc = doc.strip_prefix(' ').unwrap_or(doc);
+            self.pending_doc.push(doc.trim_end().to_string());
+        }
+    }
+    /// Skips a `/* */` comment, block comments can be nested.
+    fn skip_block_comment(&mut self) -> Result<(), LexerError> {
+        let mut depth = 0usize;
+        loop {
+            match (self.current_token, self.peek_char()) {
+                ('\0', _) => {
+                    return Err(LexerError::UnterminatedComment {
+                        span: self.token_span(),
+                    });
+                }
+                ('/', '*') => {
+                    depth += 1;
+                    self.advance();
+                    self.advance();
+                }
+                ('*', '/') => {
+                    depth -= 1;
+                    self.advance();
+                    self.advance();
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                _ => self.advance(),
+            }
+        }
+    }
     fn create_number_token(&mut self) -> Result<Token, LexerError> {
         let mut number_buffer: String = String::new();
         let mut dot_count: usize = 0;
-        while self.current_token.is_numeric() || self.current_token == '.' {
+        // A number after `.` is a tuple index, `t.0.1` is `t.0` and then `.1`
+        let after_dot = matches!(self.final_tokens.last(), Some(token) if token.token_kind == TokenKind::DOT);
+        while self.current_token.is_numeric()
+            || (!after_dot && self.current_token == '.' && self.peek_char() != '.')
+        {
             if self.current_token == '.' {
                 if dot_count < 1 {
                     dot_count += 1;
                     number_buffer.push('.')
                 } else {
-                    return Err(LexerError::MoreDotInANumber);
+                    return Err(LexerError::MoreDotInANumber {
+                        span: self.current_span(),
+                    });
                 }
             } else {
                 number_buffer.push(self.current_token);
             }
             self.advance();
         }
-        Ok(Token {
-            token_kind: if dot_count < 1 { NUMB } else { FLOAT },
-            token_value: number_buffer,
-        })
+        Ok(self.make_token(
+            if dot_count < 1 { NUMB } else { FLOAT },
+            number_buffer,
+        ))
+    }
+    /// Reads a loop label like `'outer`, the token value is the name without the quote.
+    fn read_label(&mut self) -> Token {
+        self.advance();
+        let mut name = String::new();
+        while self.current_token.is_alphanumeric() || self.current_token == '_' {
+            name.push(self.current_token);
+            self.advance();
+        }
+        self.make_token(TokenKind::LABEL, name)
+    }
+    fn is_double_operator(&self) -> bool {
+        matches!(
+            (self.current_token, self.peek_char()),
+            ('=' | '!' | '<' | '>', '=') | ('=', '>') | ('&', '&') | ('|', '|')
+        )
     }
     fn create_text_token(&mut self) -> Token {
         let mut text_buffer: String = String::new();
         while self.current_token.is_alphabetic()
             || self.current_token.is_numeric()
-            || self.current_token == '!'
+            || (self.current_token == '!' && self.peek_char() != '=')
             || self.current_token == '_'
         {
             text_buffer.push(self.current_token);
             self.advance()
         }
-        match text_buffer.as_str() {
-            "var" => Token {
-                token_kind: VAR,
-                token_value: text_buffer,
-            },
-            "fnc" => Token {
-                token_kind: FNC,
-                token_value: text_buffer,
-            },
-            "str" => Token {
-                token_kind: STR,
-                token_value: text_buffer,
-            },
-            "const" => Token {
-                token_kind: CONST,
-                token_value: text_buffer,
-            },
-            "true" => Token {
-                token_value: text_buffer,
-                token_kind: TRUE,
-            },
-            "false" => Token {
-                token_value: text_buffer,
-                token_kind: FALSE,
-            },
-            "if" => Token {
-                token_kind: IF,
-                token_value: text_buffer,
-            },
-            "else" => Token {
-                token_kind: ELSE,
-                token_value: text_buffer,
-            },
-            "loop" => Token {
-                token_kind: LOOP,
-                token_value: text_buffer,
-            },
-            "while" => Token {
-                token_kind: WHILE,
-                token_value: text_buffer,
-            },
-            "as"=>Token{
-                token_kind:AS,
-                token_value:text_buffer
-            },
-            "undef" => Token {
-                token_kind: TokenKind::UNDEF,
-                token_value: text_buffer,
-            },
-            _ => Token {
-                token_kind: IDENTIFIER,
-                token_value: text_buffer,
-            },
-        }
+        let token_kind = match text_buffer.as_str() {
+            "var" => VAR,
+            "fnc" => FNC,
+            "struct" => TokenKind::STRUCT,
+            "impl" => TokenKind::IMPL,
+            "enum" => TokenKind::ENUM,
+            "is" => TokenKind::IS,
+            "str" => STR,
+            "const" => CONST,
+            "true" => TRUE,
+            "false" => FALSE,
+            "if" => IF,
+            "else" => ELSE,
+            "loop" => LOOP,
+            "while" => WHILE,
+            "for" => TokenKind::FOR,
+            "match" => TokenKind::MATCH,
+            "in" => TokenKind::IN,
+            "step" => TokenKind::STEP,
+            "break" => TokenKind::BREAK,
+            "continue" => TokenKind::CONTINUE,
+            "return" => TokenKind::RETURN,
+            "try" => TokenKind::TRY,
+            "catch" => TokenKind::CATCH,
+            "throw" => TokenKind::THROW,
+            "as" => AS,
+            "undef" => TokenKind::UNDEF,
+            "and" => TokenKind::AND,
+            "or" => TokenKind::OR,
+            "not" => TokenKind::NOT,
+            _ => IDENTIFIER,
+        };
+        self.make_token(token_kind, text_buffer)
     }
 
+    /// Reads a `"..."` or `"""..."""` string. A string containing `{expr}`
+    /// is split into `INTERPOLATIONSTART`, text parts as `STRING` tokens with
+    /// the tokens of each embedded expression between them, and
+    /// `INTERPOLATIONEND`.
+    fn read_string(&mut self) -> Result<(), LexerError> {
+        let multiline = self.peek_nth(1) == '"' && self.peek_nth(2) == '"';
+        if multiline {
+            for _ in 0..3 {
+                self.advance();
+            }
+            // A line break right after the opening quotes is not part of the value
+            if self.current_token == '\n' {
+                self.advance();
+            } else if self.current_token == '\r' && self.peek_char() == '\n' {
+                self.advance();
+                self.advance();
+            }
+        } else {
+            self.advance();
+        }
 
+        let mut parts: Vec<(String, Vec<Token>)> = Vec::new();
+        let mut value = String::new();
+        loop {
+            match self.current_token {
+                '\0' => {
+                    return Err(LexerError::UnterminatedString {
+                        text: value,
+                        span: self.token_span(),
+                    });
+                }
+                '"' if !multiline => {
+                    self.advance();
+                    break;
+                }
+                '"' if self.peek_nth(1) == '"' && self.peek_nth(2) == '"' => {
+                    for _ in 0..3 {
+                        self.advance();
+                    }
+                    break;
+                }
+                '\\' => value.push(self.read_escape()?),
+                '{' => {
+                    let tokens = self.read_interpolation()?;
+                    parts.push((std::mem::take(&mut value), tokens));
+                }
+                c => {
+                    value.push(c);
+                    self.advance();
+                }
+            }
+        }
 
-fn read_string(&mut self) -> Result<Token, LexerError> {
-    self.advance();
-
-    let mut value = String::new();
-    while self.current_token != '"' && self.current_token != '\0' {
-        value.push(self.current_token);
-        self.advance();
-    }
-
-    if self.current_token == '\0' {
-        return Err(LexerError::UnterminatedString { text: value });
+        if parts.is_empty() {
+            self.push_token(TokenKind::STRING, value);
+            return Ok(());
+        }
+        self.push_token(TokenKind::INTERPOLATIONSTART, String::new());
+        for (text, tokens) in parts {
+            self.push_token(TokenKind::STRING, text);
+            self.final_tokens.extend(tokens);
+        }
+        self.push_token(TokenKind::STRING, value);
+        self.push_token(TokenKind::INTERPOLATIONEND, String::new());
+        Ok(())
     }
 
-    self.advance();
-    Ok(Token {
-        token_kind: TokenKind::STRING,
-        token_value: value,
-    })
-}
-
-
+    /// Tokenizes the expression of a `{...}` inside a string literal.
+    fn read_interpolation(&mut self) -> Result<Vec<Token>, LexerError> {
+        self.advance(); // {
+        let (line, column, start) = (self.line, self.column, self.byte_pos);
+        let mut text = String::new();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        loop {
+            match self.current_token {
+                '\0' => {
+                    return Err(LexerError::UnterminatedString {
+                        text,
+                        span: self.token_span(),
+                    });
+                }
+                '}' if depth == 0 && !in_string => {
+                    self.advance();
+                    break;
+                }
+                '\\' if in_string => {
+                    text.push(self.current_token);
+                    self.advance();
+                }
+                '"' => in_string = !in_string,
+                '{' if !in_string => depth += 1,
+                '}' if !in_string => depth -= 1,
+                _ => {}
+            }
+            text.push(self.current_token);
+            self.advance();
+        }
 
+        if text.trim().is_empty() {
+            return Err(LexerError::EmptyInterpolation {
+                span: Span {
+                    file: self.file.clone(),
+                    line,
+                    column: column - 1,
+                    start: start - 1,
+                    end: self.byte_pos,
+                },
+            });
+        }
+        let mut expression = Tokenizer::new(text, &self.file);
+        expression.line = line;
+        expression.column = column;
+        expression.byte_pos = start;
+        let mut tokens = expression.tokenize()?.clone();
+        tokens.pop(); // EOF
+        Ok(tokens)
+    }
 
+    /// Reads `r"..."` or `r#"..."#`, escapes are not processed. The string ends
+    /// at a quote followed by as many `#` as were used to open it.
+    fn read_raw_string(&mut self) -> Result<Token, LexerError> {
+        self.advance(); // r
+        let mut hashes = 0;
+        while self.current_token == '#' {
+            hashes += 1;
+            self.advance();
+        }
+        if self.current_token != '"' {
+            return Err(LexerError::UnknownToken {
+                wrong_token: format!("r{}", "#".repeat(hashes)),
+                span: self.token_span(),
+            });
+        }
+        self.advance();
 
+        let mut value = String::new();
+        loop {
+            if self.current_token == '\0' {
+                return Err(LexerError::UnterminatedString {
+                    text: value,
+                    span: self.token_span(),
+                });
+            }
+            if self.current_token == '"' && (1..=hashes).all(|n| self.peek_nth(n) == '#') {
+                for _ in 0..=hashes {
+                    self.advance();
+                }
+                return Ok(self.make_token(TokenKind::STRING, value));
+            }
+            value.push(self.current_token);
+            self.advance();
+        }
+    }
 
+    /// Decodes the escape sequence starting at the current `\\`.
+    fn read_escape(&mut self) -> Result<char, LexerError> {
+        let (line, column, start) = (self.line, self.column, self.byte_pos);
+        self.advance(); // backslash
+        let mut sequence = format!("\\{}", self.current_token);
+        let decoded = match self.current_token {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '"' => Some('"'),
+            '\'' => Some('\''),
+            '\\' => Some('\\'),
+            '{' => Some('{'),
+            '}' => Some('}'),
+            'u' if self.peek_char() == '{' => {
+                self.advance();
+                let mut digits = String::new();
+                while self.peek_char().is_ascii_hexdigit() {
+                    self.advance();
+                    digits.push(self.current_token);
+                }
+                let closed = self.peek_char() == '}';
+                if closed {
+                    self.advance();
+                }
+                sequence = format!("\\u{{{}{}", digits, if closed { "}" } else { "" });
+                if closed && (1..=6).contains(&digits.len()) {
+                    u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        if self.current_token != '\0' {
+            self.advance();
+        }
+        decoded.ok_or_else(|| LexerError::InvalidEscape {
+            sequence,
+            span: Span {
+                file: self.file.clone(),
+                line,
+                column,
+                start,
+                end: self.byte_pos,
+            },
+        })
+    }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     //MATH
@@ -9,25 +11,47 @@ pub enum TokenKind {
     RIGHTPAREN,
     OPENINGBRACE,
     CLOSINGBRACE,
+    LEFTBRACKET,
+    RIGHTBRACKET,
     EQUAL,
     MODULO,
     //BOOLEAN
     GREATER,
     LESS,
+    GREATEREQUAL,
+    LESSEQUAL,
+    EQUALEQUAL,
+    NOTEQUAL,
+    AND,
+    OR,
+    NOT,
     //VALUES
     NUMB,
     FLOAT,
     STRING,
     IDENTIFIER,
+    INTERPOLATIONSTART,
+    INTERPOLATIONEND,
+    LABEL,
     //MISC
     COMMA,
     COLON,
     SEMICOLON,
+    DOT,
+    DOTDOT,
+    DOTDOTEQUAL,
+    FATARROW,
+    QUESTION,
+    PIPE,
     //VALUES
     TRUE,
     FALSE,
     //KEYWORDS
     FNC,
+    STRUCT,
+    IMPL,
+    ENUM,
+    IS,
     VAR,
     CONST,
     STR,
@@ -35,6 +59,16 @@ pub enum TokenKind {
     ELSE,
     LOOP,
     WHILE,
+    FOR,
+    IN,
+    MATCH,
+    STEP,
+    BREAK,
+    CONTINUE,
+    RETURN,
+    TRY,
+    CATCH,
+    THROW,
     UNDEF,
     AS,
     //EOF
@@ -42,8 +76,37 @@ pub enum TokenKind {
     VALUE,
 }
 
+/// Location of a piece of source code.
+///
+/// `line` and `column` are 1-based and count characters, `start` and `end`
+/// are byte offsets into the source file (`end` is exclusive).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Span {
+    pub file: Arc<str>,
+    pub line: usize,
+    pub column: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Span reaching from the start of `self` to the end of `other`.
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column,
+            start: self.start,
+            end: other.end.max(self.end),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Token {
     pub token_kind: TokenKind,
     pub token_value: String,
+    pub span: Span,
+    /// Text of the `///` doc comments directly in front of this token.
+    pub doc_comment: Option<String>,
 }
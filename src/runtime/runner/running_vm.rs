@@ -6,7 +6,10 @@ pub fn run_code(path: &str) {
     let program_time_start = Instant::now();
     let mut vm: VM = VM::from_file(path).unwrap();
     println!("Program:");
-    vm.run().unwrap();
+    if let Err(e) = vm.run() {
+        eprintln!("\n\x1b[1;31mRuntime error:\x1b[0m {}", e);
+        std::process::exit(1);
+    }
     let elapsed = program_time_start.elapsed();
     let seconds = elapsed.as_secs_f32();
     println!(
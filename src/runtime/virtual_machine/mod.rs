@@ -1,4 +1,5 @@
 pub mod pre_parsing;
 mod value;
 mod variables;
+#[allow(clippy::module_inception)]
 pub mod virtual_machine;
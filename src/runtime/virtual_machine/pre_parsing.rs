@@ -46,6 +46,113 @@ impl BytecodeLoader {
                     Instructions::SaveVar(name)
                 }
 
+                instructions::LOAD_GLOBAL => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::LoadGlobal(name)
+                }
+
+                instructions::STORE_GLOBAL => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::SaveGlobal(name)
+                }
+
+                instructions::LOAD_VAR_INDEX => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::LoadVarIndex(name)
+                }
+
+                instructions::STORE_VAR_INDEX => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::StoreVarIndex(name)
+                }
+
+                instructions::PUSH_VAR => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::PushVar(name)
+                }
+
+                instructions::LOAD_GLOBAL_INDEX => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::LoadGlobalIndex(name)
+                }
+
+                instructions::STORE_GLOBAL_INDEX => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::StoreGlobalIndex(name)
+                }
+
+                instructions::PUSH_GLOBAL => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    Instructions::PushGlobal(name)
+                }
+
+                instructions::CALL => {
+                    let addr = self.read_u16()? as usize;
+                    let argc = self.read_u8()? as usize;
+                    Instructions::Call(addr, argc)
+                }
+                instructions::RETURN => Instructions::Return,
+                instructions::RETURN_VALUE => Instructions::ReturnValue,
+                instructions::POP => Instructions::Pop,
+
+                instructions::MAKE_STRUCT => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    let count = self.read_u8()? as usize;
+                    Instructions::MakeStruct(name, count)
+                }
+                instructions::LOAD_FIELD => Instructions::LoadField(self.read_u8()? as usize),
+                instructions::STORE_FIELD => Instructions::StoreField(self.read_u8()? as usize),
+
+                instructions::MAKE_ARRAY => Instructions::MakeArray(self.read_u32()? as usize),
+                instructions::LOAD_INDEX => Instructions::LoadIndex,
+                instructions::STORE_INDEX => Instructions::StoreIndex,
+                instructions::LEN => Instructions::Len,
+                instructions::ARRAY_PUSH => Instructions::ArrayPush,
+
+                instructions::MAKE_ENUM => {
+                    let len = self.read_u32()? as usize;
+                    let name = self.read_string(len)?;
+                    let tag = self.read_u8()? as usize;
+                    let count = self.read_u8()? as usize;
+                    Instructions::MakeEnum(name, tag, count)
+                }
+                instructions::IS_VARIANT => Instructions::IsVariant(self.read_u8()? as usize),
+                instructions::LOAD_PAYLOAD => Instructions::LoadPayload(self.read_u8()? as usize),
+
+                instructions::MAKE_MAP => Instructions::MakeMap(self.read_u32()? as usize),
+                instructions::MAP_HAS => Instructions::MapHas,
+                instructions::MAP_REMOVE => Instructions::MapRemove,
+                instructions::MAP_KEYS => Instructions::MapKeys,
+
+                instructions::MAKE_TUPLE => Instructions::MakeTuple(self.read_u8()? as usize),
+
+                instructions::PUSH_UNDEF => Instructions::PushUndef,
+
+                instructions::PUSH_HANDLER => Instructions::PushHandler(self.read_u16()? as usize),
+                instructions::POP_HANDLER => Instructions::PopHandler,
+                instructions::THROW => Instructions::Throw,
+
+                instructions::MAKE_CLOSURE => {
+                    let addr = self.read_u16()? as usize;
+                    let count = self.read_u8()?;
+                    let mut captures = Vec::new();
+                    for _ in 0..count {
+                        let len = self.read_u32()? as usize;
+                        captures.push(self.read_string(len)?);
+                    }
+                    Instructions::MakeClosure(addr, captures)
+                }
+                instructions::CALL_INDIRECT => Instructions::CallIndirect(self.read_u8()? as usize),
+
                 instructions::PUSH_BOOL => {
                     let value = self.read_u8()? != 0;
                     Instructions::PushBool(value)
@@ -56,6 +163,7 @@ impl BytecodeLoader {
                     Instructions::PushNumber(value)
                 }
 
+                instructions::TO_STRING => Instructions::ToString,
                 instructions::WRITE_LN=> Instructions::WriteLnLastOnStack,
                 instructions::WRITE => Instructions::WriteLastOnStack,
                 instructions::PROCESS_EXIT => Instructions::ProcessExit,
@@ -76,6 +184,11 @@ impl BytecodeLoader {
                 instructions::GREATER => Instructions::GreaterThan,
                 instructions::LESS => Instructions::LessThan,
                 instructions::EQUAL => Instructions::Equal,
+                instructions::NOT_EQUAL => Instructions::NotEqual,
+                instructions::LESS_EQUAL => Instructions::LessEqual,
+                instructions::GREATER_EQUAL => Instructions::GreaterEqual,
+                instructions::NOT => Instructions::Not,
+                instructions::NEGATE => Instructions::Negate,
                 instructions::READ_INPUT => Instructions::ReadInput,
 
                 instructions::HALT => Instructions::Halt,
@@ -1,7 +1,86 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum Value {
     StringValue(String),
     Number(f32),
     Bool(bool),
     Array(Vec<Value>),
+    /// Fields are stored in the order of the struct definition
+    Struct { name: String, fields: Vec<Value> },
+    /// Variant of an enum, `tag` is its position in the enum definition
+    Enum {
+        name: String,
+        tag: usize,
+        payload: Vec<Value>,
+    },
+    /// Entries are kept sorted by key, so iterating a map always gives the same order
+    Map(BTreeMap<MapKey, Value>),
+    Tuple(Vec<Value>),
+    /// Empty value of an optional, set values are stored as they are
+    Undef,
+    /// Function or closure, with the values it captured by name
+    Function {
+        address: usize,
+        captures: Vec<(String, Value)>,
+    },
+}
+
+/// Key of a `Value::Map`, numbers are ordered before strings.
+#[derive(Debug, Clone)]
+pub enum MapKey {
+    Number(f32),
+    StringValue(String),
+}
+
+impl Ord for MapKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (MapKey::Number(a), MapKey::Number(b)) => a.total_cmp(b),
+            (MapKey::StringValue(a), MapKey::StringValue(b)) => a.cmp(b),
+            (MapKey::Number(_), MapKey::StringValue(_)) => Ordering::Less,
+            (MapKey::StringValue(_), MapKey::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+impl PartialOrd for MapKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for MapKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for MapKey {}
+
+impl TryFrom<Value> for MapKey {
+    type Error = String;
+    fn try_from(value: Value) -> Result<Self, String> {
+        match value {
+            Value::Number(n) => Ok(MapKey::Number(n)),
+            Value::StringValue(s) => Ok(MapKey::StringValue(s)),
+            other => Err(format!("Map key must be a number or string, found {:?}", other)),
+        }
+    }
+}
+impl From<MapKey> for Value {
+    fn from(key: MapKey) -> Self {
+        match key {
+            MapKey::Number(n) => Value::Number(n),
+            MapKey::StringValue(s) => Value::StringValue(s),
+        }
+    }
+}
+impl fmt::Display for MapKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapKey::Number(n) => write!(f, "{}", n),
+            MapKey::StringValue(s) => write!(f, "\"{}\"", s),
+        }
+    }
 }
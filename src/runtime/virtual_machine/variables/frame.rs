@@ -0,0 +1,12 @@
+use std::collections::HashMap;
+
+use crate::runtime::virtual_machine::variables::variable::Variable;
+
+/// One active function call.
+#[derive(Debug)]
+pub struct Frame {
+    pub return_address: usize,
+    /// Stack height before the arguments were pushed, restored on return
+    pub stack_base: usize,
+    pub locals: HashMap<String, Variable>,
+}
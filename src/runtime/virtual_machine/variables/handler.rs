@@ -0,0 +1,10 @@
+/// One active `try` block.
+#[derive(Debug)]
+pub struct Handler {
+    /// Address of the `catch` block
+    pub catch_address: usize,
+    /// Stack height when the `try` block started, restored when an error is caught
+    pub stack_height: usize,
+    /// Number of active calls when the `try` block started
+    pub frame_depth: usize,
+}
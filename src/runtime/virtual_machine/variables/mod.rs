@@ -1 +1,3 @@
-pub mod variable;
\ No newline at end of file
+pub mod frame;
+pub mod handler;
+pub mod variable;
@@ -3,8 +3,11 @@ use {
         backend::compiler::instructions::Instructions,
         runtime::virtual_machine::{
             pre_parsing::BytecodeLoader,
-            value::Value::{self, Bool, Number, StringValue},
-            variables::variable::Variable,
+            value::{
+                MapKey,
+                Value::{self, Bool, Number, StringValue},
+            },
+            variables::{frame::Frame, handler::Handler, variable::Variable},
         },
     },
     std::{collections::HashMap, error::Error, string::String},
@@ -15,8 +18,14 @@ pub struct VM {
     pub stack: Vec<Value>,
     pub instructions: Vec<Instructions>,
     pub variables: HashMap<String, Variable>,
+    pub frames: Vec<Frame>,
+    /// Active `try` blocks, innermost last
+    pub handlers: Vec<Handler>,
 }
 
+/// Maximum depth of nested function calls before the VM gives up.
+const MAX_CALL_DEPTH: usize = 10_000;
+
 impl VM {
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Box<dyn std::error::Error>> {
         let instructions = BytecodeLoader::from_bytes(bytes)?;
@@ -25,6 +34,8 @@ impl VM {
             stack: Vec::new(),
             instructions,
             variables: std::collections::HashMap::new(),
+            frames: Vec::new(),
+            handlers: Vec::new(),
         })
     }
 
@@ -34,6 +45,17 @@ impl VM {
     }
 
     pub fn run(&mut self) -> Result<(), String> {
+        loop {
+            match self.execute() {
+                Ok(()) => return Ok(()),
+                // Failing instructions can be caught like a `throw`
+                Err(message) => self.throw(StringValue(message))?,
+            }
+        }
+    }
+
+    /// Runs instructions from `ip` until the program halts or an instruction fails.
+    fn execute(&mut self) -> Result<(), String> {
         loop {
             if self.ip >= self.instructions.len() {
                 return Err("Unexpected EOF".into());
@@ -106,12 +128,37 @@ impl VM {
                     }
                     self.ip += 1;
                 }
+                Instructions::Negate => {
+                    match self.pop()? {
+                        Number(n) => self.stack.push(Number(-n)),
+                        _ => return Err("Type error: unary '-' expects a number".into()),
+                    }
+                    self.ip += 1;
+                }
                 Instructions::PushString(s) => {
                     self.stack.push(StringValue(s));
                     self.ip += 1;
                 }
 
                 Instructions::LoadVar(name) => {
+                    let value = self
+                        .current_variables()
+                        .get(&name)
+                        .ok_or_else(|| format!("Variable '{}' not found", name))?
+                        .value
+                        .clone();
+                    self.stack.push(value);
+                    self.ip += 1;
+                }
+
+                Instructions::SaveVar(name) => {
+                    let value = self.pop()?;
+                    let var = Variable { value };
+                    self.current_variables().insert(name, var);
+                    self.ip += 1;
+                }
+
+                Instructions::LoadGlobal(name) => {
                     let variable = self
                         .variables
                         .get(&name)
@@ -120,10 +167,337 @@ impl VM {
                     self.ip += 1;
                 }
 
-                Instructions::SaveVar(name) => {
+                Instructions::SaveGlobal(name) => {
                     let value = self.pop()?;
-                    let var = Variable { value };
-                    self.variables.insert(name, var);
+                    self.variables.insert(name, Variable { value });
+                    self.ip += 1;
+                }
+
+                Instructions::LoadVarIndex(name) => {
+                    let index = self.pop()?;
+                    let element = Self::element(self.variable_mut(&name, false)?, index)?;
+                    self.stack.push(element);
+                    self.ip += 1;
+                }
+
+                Instructions::LoadGlobalIndex(name) => {
+                    let index = self.pop()?;
+                    let element = Self::element(self.variable_mut(&name, true)?, index)?;
+                    self.stack.push(element);
+                    self.ip += 1;
+                }
+
+                Instructions::StoreVarIndex(name) => {
+                    let index = self.pop()?;
+                    let value = self.pop()?;
+                    Self::set_element(self.variable_mut(&name, false)?, index, value)?;
+                    self.ip += 1;
+                }
+
+                Instructions::StoreGlobalIndex(name) => {
+                    let index = self.pop()?;
+                    let value = self.pop()?;
+                    Self::set_element(self.variable_mut(&name, true)?, index, value)?;
+                    self.ip += 1;
+                }
+
+                Instructions::PushVar(name) => {
+                    let value = self.pop()?;
+                    Self::push_element(self.variable_mut(&name, false)?, value)?;
+                    self.ip += 1;
+                }
+
+                Instructions::PushGlobal(name) => {
+                    let value = self.pop()?;
+                    Self::push_element(self.variable_mut(&name, true)?, value)?;
+                    self.ip += 1;
+                }
+
+                Instructions::Call(addr, argc) => {
+                    if self.frames.len() >= MAX_CALL_DEPTH {
+                        return Err("Stack overflow: too many nested function calls".into());
+                    }
+                    let stack_base = self
+                        .stack
+                        .len()
+                        .checked_sub(argc)
+                        .ok_or("Stack underflow")?;
+                    self.frames.push(Frame {
+                        return_address: self.ip + 1,
+                        stack_base,
+                        locals: HashMap::new(),
+                    });
+                    self.ip = addr;
+                }
+
+                Instructions::MakeClosure(address, names) => {
+                    let mut captures = Vec::new();
+                    for name in names {
+                        let value = self
+                            .current_variables()
+                            .get(&name)
+                            .ok_or_else(|| format!("Variable '{}' not found", name))?
+                            .value
+                            .clone();
+                        captures.push((name, value));
+                    }
+                    self.stack.push(Value::Function { address, captures });
+                    self.ip += 1;
+                }
+
+                Instructions::CallIndirect(argc) => {
+                    if self.frames.len() >= MAX_CALL_DEPTH {
+                        return Err("Stack overflow: too many nested function calls".into());
+                    }
+                    let args_start = self
+                        .stack
+                        .len()
+                        .checked_sub(argc)
+                        .ok_or("Stack underflow")?;
+                    let args = self.stack.split_off(args_start);
+                    let (address, captures) = match self.pop()? {
+                        Value::Function { address, captures } => (address, captures),
+                        other => return Err(format!("Cannot call {:?}", other)),
+                    };
+                    let stack_base = self.stack.len();
+                    self.stack.extend(args);
+                    // Captured values start out as locals of the call
+                    let locals = captures
+                        .into_iter()
+                        .map(|(name, value)| (name, Variable { value }))
+                        .collect();
+                    self.frames.push(Frame {
+                        return_address: self.ip + 1,
+                        stack_base,
+                        locals,
+                    });
+                    self.ip = address;
+                }
+
+                Instructions::Return => {
+                    let frame = self.frames.pop().ok_or("Return outside of a function")?;
+                    self.stack.truncate(frame.stack_base);
+                    self.ip = frame.return_address;
+                    self.end_call_handlers();
+                }
+
+                Instructions::ReturnValue => {
+                    let value = self.pop()?;
+                    let frame = self.frames.pop().ok_or("Return outside of a function")?;
+                    self.stack.truncate(frame.stack_base);
+                    self.stack.push(value);
+                    self.ip = frame.return_address;
+                    self.end_call_handlers();
+                }
+
+                Instructions::PushHandler(catch_address) => {
+                    self.handlers.push(Handler {
+                        catch_address,
+                        stack_height: self.stack.len(),
+                        frame_depth: self.frames.len(),
+                    });
+                    self.ip += 1;
+                }
+
+                Instructions::PopHandler => {
+                    self.handlers.pop().ok_or("PopHandler outside of a try block")?;
+                    self.ip += 1;
+                }
+
+                Instructions::Throw => {
+                    let error = self.pop()?;
+                    self.throw(error)?;
+                }
+
+                Instructions::MakeStruct(name, count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count)
+                        .ok_or("Stack underflow")?;
+                    let fields = self.stack.split_off(start);
+                    self.stack.push(Value::Struct { name, fields });
+                    self.ip += 1;
+                }
+
+                Instructions::LoadField(idx) => {
+                    match self.pop()? {
+                        Value::Struct { mut fields, name } => {
+                            if idx >= fields.len() {
+                                return Err(format!("{} has no field {}", name, idx));
+                            }
+                            self.stack.push(fields.swap_remove(idx));
+                        }
+                        Value::Tuple(mut elements) if idx < elements.len() => {
+                            self.stack.push(elements.swap_remove(idx));
+                        }
+                        other => return Err(format!("Cannot read a field of {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::StoreField(idx) => {
+                    let value = self.pop()?;
+                    match self.pop()? {
+                        Value::Struct { mut fields, name } => {
+                            let field = fields
+                                .get_mut(idx)
+                                .ok_or_else(|| format!("{} has no field {}", name, idx))?;
+                            *field = value;
+                            self.stack.push(Value::Struct { name, fields });
+                        }
+                        Value::Tuple(mut elements) if idx < elements.len() => {
+                            elements[idx] = value;
+                            self.stack.push(Value::Tuple(elements));
+                        }
+                        other => return Err(format!("Cannot set a field of {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::MakeArray(count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count)
+                        .ok_or("Stack underflow")?;
+                    let elements = self.stack.split_off(start);
+                    self.stack.push(Value::Array(elements));
+                    self.ip += 1;
+                }
+
+                Instructions::LoadIndex => {
+                    let index = self.pop()?;
+                    let container = self.pop()?;
+                    self.stack.push(Self::element(&container, index)?);
+                    self.ip += 1;
+                }
+
+                Instructions::StoreIndex => {
+                    let index = self.pop()?;
+                    let value = self.pop()?;
+                    let mut container = self.pop()?;
+                    Self::set_element(&mut container, index, value)?;
+                    self.stack.push(container);
+                    self.ip += 1;
+                }
+
+                Instructions::Len => {
+                    let len = match self.pop()? {
+                        Value::Array(elements) => elements.len(),
+                        Value::Map(entries) => entries.len(),
+                        StringValue(s) => s.chars().count(),
+                        other => return Err(format!("Cannot take the length of {:?}", other)),
+                    };
+                    self.stack.push(Number(len as f32));
+                    self.ip += 1;
+                }
+
+                Instructions::ArrayPush => {
+                    let value = self.pop()?;
+                    let mut array = self.pop()?;
+                    Self::push_element(&mut array, value)?;
+                    self.stack.push(array);
+                    self.ip += 1;
+                }
+
+                Instructions::MakeEnum(name, tag, count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count)
+                        .ok_or("Stack underflow")?;
+                    let payload = self.stack.split_off(start);
+                    self.stack.push(Value::Enum { name, tag, payload });
+                    self.ip += 1;
+                }
+
+                Instructions::IsVariant(expected) => {
+                    match self.pop()? {
+                        Value::Enum { tag, .. } => self.stack.push(Bool(tag == expected)),
+                        other => return Err(format!("Cannot check the variant of {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::LoadPayload(idx) => {
+                    match self.pop()? {
+                        Value::Enum { mut payload, name, tag } => {
+                            if idx >= payload.len() {
+                                return Err(format!("Variant {} of {} has no value {}", tag, name, idx));
+                            }
+                            self.stack.push(payload.swap_remove(idx));
+                        }
+                        other => return Err(format!("Cannot read the payload of {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::MakeTuple(count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count)
+                        .ok_or("Stack underflow")?;
+                    let elements = self.stack.split_off(start);
+                    self.stack.push(Value::Tuple(elements));
+                    self.ip += 1;
+                }
+
+                Instructions::MakeMap(count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count * 2)
+                        .ok_or("Stack underflow")?;
+                    let mut entries = std::collections::BTreeMap::new();
+                    let mut values = self.stack.split_off(start).into_iter();
+                    while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                        entries.insert(MapKey::try_from(key)?, value);
+                    }
+                    self.stack.push(Value::Map(entries));
+                    self.ip += 1;
+                }
+
+                Instructions::MapHas => {
+                    let key = MapKey::try_from(self.pop()?)?;
+                    match self.pop()? {
+                        Value::Map(entries) => self.stack.push(Bool(entries.contains_key(&key))),
+                        other => return Err(format!("Cannot look up a key in {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::MapRemove => {
+                    let key = MapKey::try_from(self.pop()?)?;
+                    match self.pop()? {
+                        Value::Map(mut entries) => {
+                            entries.remove(&key);
+                            self.stack.push(Value::Map(entries));
+                        }
+                        other => return Err(format!("Cannot remove a key from {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::MapKeys => {
+                    match self.pop()? {
+                        Value::Map(entries) => {
+                            let keys = entries.into_keys().map(Value::from).collect();
+                            self.stack.push(Value::Array(keys));
+                        }
+                        other => return Err(format!("Cannot list the keys of {:?}", other)),
+                    }
+                    self.ip += 1;
+                }
+
+                Instructions::Pop => {
+                    self.pop()?;
+                    self.ip += 1;
+                }
+
+                Instructions::PushUndef => {
+                    self.stack.push(Value::Undef);
                     self.ip += 1;
                 }
 
@@ -137,11 +511,23 @@ impl VM {
                     self.ip += 1;
                 }
 
+                Instructions::ToString => {
+                    let text = match self.pop()? {
+                        StringValue(s) => s,
+                        Number(n) => n.to_string(),
+                        Bool(b) => b.to_string(),
+                        other => return Err(format!("Cannot convert {:?} to string", other)),
+                    };
+                    self.stack.push(StringValue(text));
+                    self.ip += 1;
+                }
+
                 Instructions::WriteLnLastOnStack => {
                     let val = self.pop()?;
                     match val {
                         StringValue(s) => println!("{}", s),
                         Number(n) => println!("{}", n),
+                        Bool(b) => println!("{}", b),
                         _ => unreachable!(),
                     }
                     self.ip += 1;
@@ -152,6 +538,7 @@ impl VM {
                     match val {
                         StringValue(s) => print!("{}", s),
                         Number(n) => print!("{}", n),
+                        Bool(b) => print!("{}", b),
                         _ => unreachable!(),
                     }
                     std::io::Write::flush(&mut std::io::stdout()).unwrap();
@@ -226,6 +613,41 @@ impl VM {
                     }
                     self.ip += 1;
                 }
+                Instructions::Not => {
+                    match self.pop()? {
+                        Bool(b) => self.stack.push(Bool(!b)),
+                        _ => return Err("Not expects boolean".into()),
+                    }
+                    self.ip += 1;
+                }
+                Instructions::NotEqual => {
+                    let right = self.pop()?;
+                    let left = self.pop()?;
+                    self.stack.push(Bool(left != right));
+                    self.ip += 1;
+                }
+                Instructions::LessEqual => {
+                    let right = self.pop()?;
+                    let left = self.pop()?;
+                    match (left, right) {
+                        (Number(l), Number(r)) => {
+                            self.stack.push(Bool(l <= r));
+                            self.ip += 1;
+                        }
+                        _ => return Err("LessEqual expects numbers".into()),
+                    }
+                }
+                Instructions::GreaterEqual => {
+                    let right = self.pop()?;
+                    let left = self.pop()?;
+                    match (left, right) {
+                        (Number(l), Number(r)) => {
+                            self.stack.push(Bool(l >= r));
+                            self.ip += 1;
+                        }
+                        _ => return Err("GreaterEqual expects numbers".into()),
+                    }
+                }
                 Instructions::ReadInput => {
                     let mut input = String::new();
                     std::io::stdin()
@@ -245,6 +667,103 @@ impl VM {
         Ok(())
     }
 
+    /// Unwinds to the innermost `try` block and continues in its `catch` with `error`,
+    /// fails with the error when there is none.
+    fn throw(&mut self, error: Value) -> Result<(), String> {
+        let message = match error {
+            StringValue(message) => message,
+            other => format!("{:?}", other),
+        };
+        let Some(handler) = self.handlers.pop() else {
+            return Err(message);
+        };
+        self.frames.truncate(handler.frame_depth);
+        self.stack.truncate(handler.stack_height);
+        self.stack.push(StringValue(message));
+        self.ip = handler.catch_address;
+        Ok(())
+    }
+
+    /// Drops the `try` blocks of a call that returned from inside of them.
+    fn end_call_handlers(&mut self) {
+        let depth = self.frames.len();
+        while self.handlers.last().is_some_and(|handler| handler.frame_depth > depth) {
+            self.handlers.pop();
+        }
+    }
+
+    /// Variables of the running function, or the globals at top level.
+    fn current_variables(&mut self) -> &mut HashMap<String, Variable> {
+        match self.frames.last_mut() {
+            Some(frame) => &mut frame.locals,
+            None => &mut self.variables,
+        }
+    }
+
+    /// Value of a variable, for the instructions that change one in place.
+    fn variable_mut(&mut self, name: &str, global: bool) -> Result<&mut Value, String> {
+        let variables = if global { &mut self.variables } else { self.current_variables() };
+        variables
+            .get_mut(name)
+            .map(|variable| &mut variable.value)
+            .ok_or_else(|| format!("Variable '{}' not found", name))
+    }
+
+    /// Copy of the element of an array at an index or of a map at a key.
+    fn element(container: &Value, index: Value) -> Result<Value, String> {
+        match container {
+            Value::Array(elements) => {
+                let idx = Self::array_index(&index, elements.len())?;
+                Ok(elements[idx].clone())
+            }
+            Value::Map(entries) => {
+                let key = MapKey::try_from(index)?;
+                entries
+                    .get(&key)
+                    .cloned()
+                    .ok_or_else(|| format!("Key {} is not in the map", key))
+            }
+            other => Err(format!("Cannot index into {:?}", other)),
+        }
+    }
+
+    /// Replaces an element of an array, or inserts or replaces the entry of a map.
+    fn set_element(container: &mut Value, index: Value, value: Value) -> Result<(), String> {
+        match container {
+            Value::Array(elements) => {
+                let idx = Self::array_index(&index, elements.len())?;
+                elements[idx] = value;
+            }
+            Value::Map(entries) => {
+                entries.insert(MapKey::try_from(index)?, value);
+            }
+            other => return Err(format!("Cannot index into {:?}", other)),
+        }
+        Ok(())
+    }
+
+    fn push_element(array: &mut Value, value: Value) -> Result<(), String> {
+        match array {
+            Value::Array(elements) => {
+                elements.push(value);
+                Ok(())
+            }
+            other => Err(format!("Cannot push to {:?}", other)),
+        }
+    }
+
+    /// Checks that `index` is a whole number inside an array of length `len`.
+    fn array_index(index: &Value, len: usize) -> Result<usize, String> {
+        match index {
+            Number(n) if n.fract() == 0.0 && *n >= 0.0 && (*n as usize) < len => Ok(*n as usize),
+            Number(n) => Err(format!(
+                "Index out of bounds: the length is {} but the index is {}",
+                len, n
+            )),
+            other => Err(format!("Array index must be a number, found {:?}", other)),
+        }
+    }
+
     fn pop(&mut self) -> Result<Value, String> {
         self.stack.pop().ok_or("Stack underflow".into())
     }
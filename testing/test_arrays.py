@@ -0,0 +1,224 @@
+import os
+import subprocess
+import time
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-arrays"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_array_literal_and_index():
+    code = """
+var a = [1, 2, 3];
+writeLn!(a[1])
+a[1] = 20;
+writeLn!("{a[0]} {a[1]} {a[2]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2\n" in stdout
+    assert "1 20 3" in stdout
+
+
+def test_len_and_push():
+    code = """
+var names: string[];
+push!(names, "a")
+push!(names, "b")
+writeLn!("{len!(names)} {names[1]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2 b" in stdout
+
+
+def test_nested_arrays():
+    code = """
+var grid: numb[][] = [[1, 2], []];
+grid[0][1] = 5;
+push!(grid[1], 7)
+writeLn!("{grid[0][1]} {grid[1][0]} {len!(grid[1])}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "5 7 1" in stdout
+
+
+def test_array_in_struct_and_function():
+    code = """
+struct Bag { items: numb[] }
+fnc sum(xs: numb[]): numb {
+    var total = 0;
+    for i in 0..len!(xs) {
+        total = total + xs[i];
+    }
+    return total;
+}
+var b = Bag { items: [] };
+push!(b.items, 3)
+push!(b.items, 4)
+b.items[0] = b.items[0] * 2;
+writeLn!(sum(b.items))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "10" in stdout
+
+
+def test_index_evaluated_once():
+    code = """
+var calls = 0;
+fnc next(): numb {
+    calls = calls + 1;
+    return 1;
+}
+var a = [0, 0];
+a[next()] = 5;
+writeLn!("{a[1]} {calls}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "5 1" in stdout
+
+
+def test_out_of_bounds():
+    code = """
+var a = [1, 2, 3];
+writeLn!(a[3])
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "out of bounds" in stderr
+
+
+def test_mixed_element_types():
+    code = """var a = [1, "two"];"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_empty_array_needs_type():
+    code = """var a = [];"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0002" in stdout
+
+
+def test_index_non_array():
+    code = """
+var n = 5;
+writeLn!(n[0])
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0030" in stdout
+
+
+def test_push_to_value():
+    code = """push!([1], 2)"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0031" in stdout
+
+
+def test_large_array_in_place():
+    code = """
+var a: numb[];
+for i in 0..10000 {
+    push!(a, i)
+}
+var above = 0;
+for i in 0..10000 {
+    a[i] = a[i] + 1;
+    if (a[i] > 5000) {
+        above = above + 1;
+    }
+}
+writeLn!("{len!(a)} {a[9999]} {above}")
+"""
+    # Copying the whole array on every access would take seconds
+    start = time.time()
+    exit_code, stdout, stderr = run_flare_code(code)
+    elapsed = time.time() - start
+    assert exit_code == 0
+    assert "10000 10000 5000" in stdout
+    assert elapsed < 5, f"took {elapsed:.1f}s"
+
+
+def test_global_array_from_function():
+    code = """
+var log: string[];
+fnc record(message: string) {
+    push!(log, message)
+    log[0] = "first";
+}
+record("a")
+record("b")
+writeLn!("{len!(log)} {log[0]} {log[1]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2 first b" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running array tests...")
+
+    tests = [
+        ("array literal and index", test_array_literal_and_index),
+        ("len and push", test_len_and_push),
+        ("nested arrays", test_nested_arrays),
+        ("array in struct and function", test_array_in_struct_and_function),
+        ("index evaluated once", test_index_evaluated_once),
+        ("out of bounds", test_out_of_bounds),
+        ("mixed element types", test_mixed_element_types),
+        ("empty array needs type", test_empty_array_needs_type),
+        ("index non array", test_index_non_array),
+        ("push to value", test_push_to_value),
+        ("large array in place", test_large_array_in_place),
+        ("global array from function", test_global_array_from_function),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
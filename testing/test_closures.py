@@ -0,0 +1,221 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-closures"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_lambda_call():
+    code = """
+var square = |x: numb| x * x;
+writeLn!(square(4))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "16" in stdout
+
+
+def test_function_argument():
+    code = """
+fnc apply(f: fnc(numb): numb, x: numb): numb {
+    return f(x);
+}
+fnc double(x: numb): numb {
+    return x * 2;
+}
+writeLn!(apply(|x: numb| x + 1, 3))
+writeLn!(apply(double, 5))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "4" in stdout
+    assert "10" in stdout
+
+
+def test_returned_closure_captures():
+    code = """
+fnc make_adder(n: numb): fnc(numb): numb {
+    return |x: numb| x + n;
+}
+var add2 = make_adder(2);
+writeLn!(add2(10))
+writeLn!(make_adder(5)(1))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "12" in stdout
+    assert "6" in stdout
+
+
+def test_nested_closures():
+    code = """
+fnc sum3(a: numb): fnc(numb): fnc(numb): numb {
+    return |b: numb| |c: numb| a + b + c;
+}
+writeLn!(sum3(100)(20)(3))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "123" in stdout
+
+
+def test_captures_are_copies():
+    code = """
+fnc run(): numb {
+    var total = 1;
+    var add = |x: numb| total + x;
+    total = 10;
+    return add(1);
+}
+writeLn!(run())
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2" in stdout
+
+
+def test_block_body_and_no_params():
+    code = """
+var abs = |x: numb|: numb {
+    if (x < 0) {
+        return 0 - x;
+    }
+    return x;
+};
+var hello = || writeLn!("hello");
+hello()
+writeLn!(abs(0 - 7))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "hello" in stdout
+    assert "7" in stdout
+
+
+def test_array_of_functions():
+    code = """
+fnc double(x: numb): numb {
+    return x * 2;
+}
+var fs: (fnc(numb): numb)[] = [double, |x: numb| x * x];
+for i in 0..2 {
+    writeLn!("r{fs[i](3)}")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "r6" in stdout
+    assert "r9" in stdout
+
+
+def test_function_type_mismatch():
+    code = """
+var f: fnc(numb): numb = |x: string| 1;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0004" in stdout
+
+
+def test_function_needs_value():
+    code = """
+var f: fnc(): numb;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0039" in stdout
+
+
+def test_not_callable():
+    code = """
+var x = 5;
+x(1)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0040" in stdout
+
+
+def test_captured_variable_is_const():
+    code = """
+fnc run() {
+    var n = 1;
+    var set = || { n = 2; };
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0009" in stdout
+
+
+def test_too_many_captures_for_bytecode():
+    declarations = "\n".join(f"    var v{i} = {i};" for i in range(256))
+    total = " + ".join(f"v{i}" for i in range(256))
+    code = f"""
+fnc make(): fnc(): numb {{
+{declarations}
+    return || {total};
+}}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many captured variables (256)" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running Closures tests...")
+
+    tests = [
+        ("lambda call", test_lambda_call),
+        ("function argument", test_function_argument),
+        ("returned closure captures", test_returned_closure_captures),
+        ("nested closures", test_nested_closures),
+        ("captures are copies", test_captures_are_copies),
+        ("block body and no params", test_block_body_and_no_params),
+        ("array of functions", test_array_of_functions),
+        ("function type mismatch", test_function_type_mismatch),
+        ("function needs value", test_function_needs_value),
+        ("not callable", test_not_callable),
+        ("captured variable is const", test_captured_variable_is_const),
+        ("too many captures for bytecode", test_too_many_captures_for_bytecode),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,175 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-comments"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_comments():
+    code = """
+// line comment
+/* block /* nested */ comment */
+/// documented constant
+const x = 10 / 2;
+writeLn!(x)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "5" in stdout
+
+
+def test_comment_markers_in_strings():
+    code = """
+writeLn!("// not a comment /* either */")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "// not a comment /* either */" in stdout
+
+
+def test_deeply_nested_block_comment():
+    code = """
+/* one /* two /* three */ still two */ still one
+   writeLn!("hidden")
+*/
+writeLn!("shown") // trailing comment
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "shown" in stdout
+    assert "hidden" not in stdout
+
+
+def test_unterminated_block_comment():
+    code = """
+/* opened /* nested */
+writeLn!("never")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Unterminated block comment" in stdout
+    assert "test.flare:2:1" in stdout
+
+
+def build_ast_dump(code: str) -> list[str]:
+    test_file = os.path.join(TEST_DIR, "test.flare")
+    with open(test_file, "w") as f:
+        f.write(code)
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", "-d", test_file, OUTPUT_NAME],
+        capture_output=True,
+        text=True,
+    )
+    assert build_proc.returncode == 0
+    lines = build_proc.stdout.splitlines()
+    start = lines.index("Program")
+    return [line.strip() for line in lines[start + 1:] if line.strip()]
+
+
+def test_doc_comment_on_function():
+    code = """
+/// Adds one
+/// to a number
+fnc inc(n: numb): numb {
+    return n + 1;
+}
+fnc dec(n: numb): numb {
+    return n - 1;
+}
+"""
+    ast = build_ast_dump(code)
+    assert 'fnc inc (doc: Some("Adds one\\nto a number"))' in ast
+    assert "fnc dec (doc: None)" in ast
+
+
+def test_doc_comment_on_variable():
+    code = """
+/// The starting value
+var start = 1;
+var other = 2;
+"""
+    ast = build_ast_dump(code)
+    assert 'var start:None (doc: Some("The starting value"))=Number(1)' in ast
+    assert "var other:None (doc: None)=Number(2)" in ast
+
+
+def test_plain_comment_keeps_doc_comment():
+    code = """
+/// Adds one
+// not part of the doc
+fnc inc(n: numb): numb {
+    return n + 1;
+}
+// plain comment
+fnc dec(n: numb): numb {
+    return n - 1;
+}
+/// Counter
+/* not part of the doc either */
+var count = 0;
+// plain comment
+var other = 1;
+"""
+    ast = build_ast_dump(code)
+    assert 'fnc inc (doc: Some("Adds one"))' in ast
+    assert "fnc dec (doc: None)" in ast
+    assert 'var count:None (doc: Some("Counter"))=Number(0)' in ast
+    assert "var other:None (doc: None)=Number(1)" in ast
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running Comments tests...")
+
+    tests = [
+        ("comments", test_comments),
+        ("comment markers in strings", test_comment_markers_in_strings),
+        ("deeply nested block comment", test_deeply_nested_block_comment),
+        ("unterminated block comment", test_unterminated_block_comment),
+        ("doc comment on function", test_doc_comment_on_function),
+        ("doc comment on variable", test_doc_comment_on_variable),
+        ("plain comment keeps doc comment", test_plain_comment_keeps_doc_comment),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
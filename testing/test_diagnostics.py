@@ -0,0 +1,126 @@
+import os
+import re
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-diagnostics"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+ANSI = re.compile(r"\x1b\[[0-9;]*m")
+
+
+def diagnostic(stdout: str) -> tuple[str, str, int, int]:
+    """Return (location, source line, caret column, caret count) of the first diagnostic"""
+    lines = ANSI.sub("", stdout).splitlines()
+    arrow = next(i for i, line in enumerate(lines) if "-->" in line)
+    location = lines[arrow].split("-->")[1].strip()
+    source = lines[arrow + 2].split("|", 1)[1][1:]
+    carets = lines[arrow + 3].split("|", 1)[1][1:]
+    column = len(carets) - len(carets.lstrip(" ")) + 1
+    return location, source, column, carets.count("^")
+
+
+def test_lexer_error_location():
+    code = """var s = "abc"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    location, source, column, count = diagnostic(stdout)
+    assert location.endswith("test.flare:1:9")
+    assert source == 'var s = "abc'
+    assert column == 9
+    assert count == 4
+
+
+def test_parser_error_location():
+    code = """var a = 1;
+var x = ;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    location, source, column, count = diagnostic(stdout)
+    assert location.endswith("test.flare:2:9")
+    assert source == "var x = ;"
+    assert column == 9
+    assert count == 1
+
+
+def test_compiler_error_location():
+    code = """var a = 1;
+
+var x: numb = "text";
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0004" in stdout
+    location, source, column, count = diagnostic(stdout)
+    assert location.endswith("test.flare:3:15")
+    assert source == 'var x: numb = "text";'
+    assert column == 15
+    assert count == len('"text"')
+
+
+def test_gutter_widens_with_line_number():
+    code = "\n" * 11 + "writeLn!(missing)\n"
+    exit_code, stdout, stderr = run_flare_code(code)
+    lines = ANSI.sub("", stdout).splitlines()
+    arrow = next(i for i, line in enumerate(lines) if "-->" in line)
+    assert lines[arrow].startswith("  -->")
+    assert lines[arrow + 2].startswith("12 | writeLn!(missing)")
+    location, source, column, count = diagnostic(stdout)
+    assert location.endswith("test.flare:12:10")
+    assert count == len("missing")
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running Diagnostics tests...")
+
+    tests = [
+        ("lexer error location", test_lexer_error_location),
+        ("parser error location", test_parser_error_location),
+        ("compiler error location", test_compiler_error_location),
+        ("gutter widens with line number", test_gutter_widens_with_line_number),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,234 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-enums"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_construct_and_match():
+    code = """
+enum Shape { Circle(flt), Rect(flt, flt), Empty }
+
+fnc area(s: Shape): flt {
+    match s {
+        Shape.Circle(r) => { return 3.0 * r * r; }
+        Shape.Rect(w, h) => { return w * h; }
+        Shape.Empty => { return 0.0; }
+    }
+}
+writeLn!("{area(Shape.Circle(2.0))} {area(Shape.Rect(2.0, 3.0))} {area(Shape.Empty)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "12 6 0" in stdout
+
+
+def test_is_check():
+    code = """
+enum Shape { Circle(flt), Empty }
+var s = Shape.Circle(1.0);
+writeLn!("{s is Shape.Circle} {s is Shape.Empty}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true false" in stdout
+
+
+def test_wildcard_binding_and_arm():
+    code = """
+enum Pair { Two(numb, numb), None }
+var p = Pair.Two(4, 5);
+match p {
+    Pair.Two(_, b) => writeLn!(b)
+    _ => writeLn!("none")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "5" in stdout
+
+
+def test_recursive_enum():
+    code = """
+fnc sum(l: List): numb {
+    match l {
+        List.Cons(v, rest) => { return v + sum(rest); }
+        List.Nil => { return 0; }
+    }
+}
+enum List { Cons(numb, List), Nil }
+var empty = List.Nil;
+writeLn!("{sum(List.Cons(1, List.Cons(2, List.Cons(3, List.Nil))))} {empty is List.Nil}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "6 true" in stdout
+
+
+def test_enum_methods_and_arrays():
+    code = """
+enum Light { Red, Green }
+impl Light {
+    fnc next(self): Light {
+        match self {
+            Light.Red => { return Light.Green; }
+            Light.Green => { return Light.Red; }
+        }
+    }
+}
+var lights = [Light.Red, Light.Green];
+writeLn!("{lights[0].next() is Light.Green} {lights[1].next() is Light.Green}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true false" in stdout
+
+
+def test_unknown_variant():
+    code = """
+enum Shape { Circle(flt) }
+var s = Shape.Square(1.0);
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0032" in stdout
+
+
+def test_wrong_payload_count():
+    code = """
+enum Shape { Rect(flt, flt) }
+var s = Shape.Rect(1.0);
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0033" in stdout
+
+
+def test_payload_type_mismatch():
+    code = """
+enum Shape { Circle(flt) }
+var s = Shape.Circle("big");
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_non_exhaustive_match():
+    code = """
+enum Shape { Circle(flt), Empty }
+var s = Shape.Empty;
+match s {
+    Shape.Circle(r) => writeLn!(r)
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0017" in stdout
+    assert "Shape.Empty" in stdout
+
+
+def test_duplicate_variant():
+    code = """
+enum Shape { Circle(flt), Circle(numb) }
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0034" in stdout
+
+
+def test_type_defined_twice():
+    code = """
+struct Shape { x: flt }
+enum Shape { Empty }
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0023" in stdout
+
+
+def test_too_many_variants_for_bytecode():
+    variants = ", ".join(f"V{i}" for i in range(257))
+    code = f"""
+enum Big {{ {variants} }}
+var b = Big.V256;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many enum variants (256)" in stdout
+
+
+def test_too_many_payload_values_for_bytecode():
+    types = ", ".join("numb" for _ in range(256))
+    values = ", ".join("1" for _ in range(256))
+    code = f"""
+enum Wide {{ Many({types}) }}
+var w = Wide.Many({values});
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many payload values (256)" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running enum tests...")
+
+    tests = [
+        ("construct and match", test_construct_and_match),
+        ("is check", test_is_check),
+        ("wildcard binding and arm", test_wildcard_binding_and_arm),
+        ("recursive enum", test_recursive_enum),
+        ("enum methods and arrays", test_enum_methods_and_arrays),
+        ("unknown variant", test_unknown_variant),
+        ("wrong payload count", test_wrong_payload_count),
+        ("payload type mismatch", test_payload_type_mismatch),
+        ("non exhaustive match", test_non_exhaustive_match),
+        ("duplicate variant", test_duplicate_variant),
+        ("type defined twice", test_type_defined_twice),
+        ("too many variants for bytecode", test_too_many_variants_for_bytecode),
+        ("too many payload values for bytecode", test_too_many_payload_values_for_bytecode),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,445 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-functions"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_function_call():
+    code = """
+fnc greet(name: string, times: numb) {
+    for i in 0..times {
+        writeLn!("hi {name} {i}")
+    }
+}
+greet("Bob", 2)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "hi Bob 0" in stdout
+    assert "hi Bob 1" in stdout
+
+
+def test_recursion_keeps_locals():
+    code = """
+fnc countdown(n: numb) {
+    if (n > 0) {
+        write!("{n} ")
+        countdown(n - 1)
+        write!("<{n}> ")
+    }
+}
+countdown(3)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "3 2 1 <1> <2> <3>" in stdout
+
+
+def test_recursive_factorial_with_global():
+    code = """
+var result = 1;
+fnc factorial(n: numb) {
+    if (n > 1) {
+        result = result * n;
+        factorial(n - 1)
+    }
+}
+factorial(5)
+writeLn!(result)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "120" in stdout
+
+
+def test_locals_do_not_leak():
+    code = """
+var i = 100;
+fnc loop_inside() {
+    for i in 0..3 {
+    }
+    var local = 5;
+}
+loop_inside()
+writeLn!(i)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "100" in stdout
+
+
+def test_wrong_argument_count():
+    code = """
+fnc f(a: numb) {
+}
+f(1, 2)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0014" in stdout
+
+
+def test_return_values():
+    code = """
+fnc fib(n: numb): numb {
+    if (n < 2) {
+        return n;
+    }
+    return fib(n - 1) + fib(n - 2);
+}
+fnc factorial(n: numb): numb {
+    if (n <= 1) {
+        return 1;
+    } else {
+        return n * factorial(n - 1);
+    }
+}
+var y = fib(10);
+writeLn!("fib {y} fact {factorial(5)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "fib 55 fact 120" in stdout
+
+
+def test_early_return():
+    code = """
+fnc print_until(x: numb) {
+    for i in 0..10 {
+        if (i == x) {
+            return;
+        }
+        write!("{i} ")
+    }
+}
+print_until(3)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "0 1 2 \n" in stdout
+
+
+def test_missing_return():
+    code = """
+fnc f(x: numb): numb {
+    if (x > 0) {
+        return 1;
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0019" in stdout
+
+
+def test_return_type_mismatch():
+    code = """
+fnc f(): numb {
+    return "text";
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_return_outside_function():
+    code = """return 5;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0018" in stdout
+
+
+def test_call_before_definition():
+    code = """
+main()
+fnc main() {
+    writeLn!("helper says {helper(2)}")
+}
+fnc helper(x: numb): numb {
+    return x * 21;
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "helper says 42" in stdout
+
+
+def test_mutual_recursion():
+    code = """
+fnc is_even(n: numb): bool {
+    if (n == 0) {
+        return true;
+    }
+    return is_odd(n - 1);
+}
+fnc is_odd(n: numb): bool {
+    if (n == 0) {
+        return false;
+    }
+    return is_even(n - 1);
+}
+writeLn!("{is_even(10)} {is_odd(7)} {is_even(3)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true true false" in stdout
+
+
+def test_duplicate_function():
+    code = """
+fnc a() {
+}
+fnc a() {
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0012" in stdout
+
+
+def test_default_and_named_arguments():
+    code = """
+fnc greet(name: string, greeting: string = "Hello"): string {
+    return "{greeting}, {name}!";
+}
+writeLn!(greet("Ann"))
+writeLn!(greet(name = "Bob"))
+writeLn!(greet("Cy", greeting = "Hi"))
+writeLn!(greet(greeting = "Yo", name = "Di"))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "Hello, Ann!" in stdout
+    assert "Hello, Bob!" in stdout
+    assert "Hi, Cy!" in stdout
+    assert "Yo, Di!" in stdout
+
+
+def test_default_type_mismatch():
+    code = """
+fnc f(x: numb = "one") {
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_unknown_named_argument():
+    code = """
+fnc f(x: numb) {
+}
+f(y = 1)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0020" in stdout
+
+
+def test_duplicate_named_argument():
+    code = """
+fnc f(x: numb) {
+}
+f(1, x = 2)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0021" in stdout
+
+
+def test_missing_argument():
+    code = """
+fnc f(x: numb, y: numb = 2) {
+}
+f(y = 3)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0022" in stdout
+
+
+def test_too_many_arguments_for_bytecode():
+    params = ", ".join(f"a{i}: numb" for i in range(256))
+    args = ", ".join("1" for _ in range(256))
+    code = f"""
+fnc wide({params}) {{
+}}
+wide({args})
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many call arguments (256)" in stdout
+
+
+def test_loop_without_break_returns():
+    code = """
+fnc first_above(limit: numb): numb {
+    var i = 0;
+    loop {
+        i = i + 1;
+        if (i * i > limit) {
+            return i;
+        }
+    }
+}
+writeLn!(first_above(10))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "4" in stdout
+
+
+def test_loop_with_break_can_fall_through():
+    code = """
+fnc f(x: numb): numb {
+    loop {
+        if (x > 1) {
+            break;
+        }
+        return 1;
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0019" in stdout
+
+
+def test_labeled_break_from_inner_loop_falls_through():
+    code = """
+fnc f(x: numb): numb {
+    'outer: loop {
+        for i in 0..3 {
+            if (i == x) {
+                break 'outer;
+            }
+        }
+        return 2;
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0019" in stdout
+
+
+def test_default_uses_definition_scope():
+    code = """
+const n: numb = 1;
+fnc f(x: numb = n): numb {
+    return x;
+}
+fnc g(): numb {
+    const n: numb = 99;
+    return f();
+}
+fnc h(): numb {
+    var n = "shadow";
+    return f();
+}
+writeLn!("{g()} {h()}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "1 1" in stdout
+
+
+def test_default_of_method_and_generic():
+    code = """
+const unit: numb = 2;
+struct Counter { count: numb }
+impl Counter {
+    fnc next(self, by: numb = unit): numb {
+        return self.count + by;
+    }
+}
+fnc pair<T>(value: T, times: numb = unit * 5): T {
+    writeLn!(times)
+    return value;
+}
+fnc run() {
+    const unit: numb = 100;
+    var c = Counter { count: 1 };
+    writeLn!(c.next())
+    writeLn!(pair("x"))
+}
+run()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "3\n10\nx" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running function tests...")
+
+    tests = [
+        ("function call", test_function_call),
+        ("recursion keeps locals", test_recursion_keeps_locals),
+        ("recursive factorial with global", test_recursive_factorial_with_global),
+        ("locals do not leak", test_locals_do_not_leak),
+        ("wrong argument count", test_wrong_argument_count),
+        ("return values", test_return_values),
+        ("early return", test_early_return),
+        ("missing return", test_missing_return),
+        ("return type mismatch", test_return_type_mismatch),
+        ("return outside function", test_return_outside_function),
+        ("call before definition", test_call_before_definition),
+        ("mutual recursion", test_mutual_recursion),
+        ("duplicate function", test_duplicate_function),
+        ("default and named arguments", test_default_and_named_arguments),
+        ("default type mismatch", test_default_type_mismatch),
+        ("unknown named argument", test_unknown_named_argument),
+        ("duplicate named argument", test_duplicate_named_argument),
+        ("missing argument", test_missing_argument),
+        ("too many arguments for bytecode", test_too_many_arguments_for_bytecode),
+        ("loop without break returns", test_loop_without_break_returns),
+        ("loop with break can fall through", test_loop_with_break_can_fall_through),
+        ("labeled break from inner loop falls through", test_labeled_break_from_inner_loop_falls_through),
+        ("default uses definition scope", test_default_uses_definition_scope),
+        ("default of method and generic", test_default_of_method_and_generic),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,251 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-generics"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_numeric_max():
+    code = """
+fnc max<T: numeric>(a: T, b: T): T {
+    if (a > b) {
+        return a;
+    }
+    return b;
+}
+writeLn!(max(3, 7))
+writeLn!(max(2.5, 1.5))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "7" in stdout
+    assert "2.5" in stdout
+
+
+def test_array_element_type():
+    code = """
+fnc first<T>(items: T[]): T {
+    return items[0];
+}
+writeLn!(first(["a", "b"]))
+writeLn!(first([4, 5]) + 1)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "a" in stdout
+    assert "5" in stdout
+
+
+def test_printable_constraint():
+    code = """
+fnc show<T: printable>(value: T) {
+    writeLn!("value: {value}")
+}
+show(4)
+show("hi")
+show(true)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "value: 4" in stdout
+    assert "value: hi" in stdout
+    assert "value: true" in stdout
+
+
+def test_inferred_from_closure():
+    code = """
+fnc map<T, U>(items: T[], f: fnc(T): U): U[] {
+    var out: U[] = [];
+    for i in 0..len!(items) {
+        push!(out, f(items[i]))
+    }
+    return out;
+}
+var lens = map(["a", "bb", "ccc"], |s: string| len!(s));
+writeLn!("last {lens[2]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "last 3" in stdout
+
+
+def test_generic_calls_generic():
+    code = """
+fnc id<T>(x: T): T {
+    return x;
+}
+fnc pair<T>(x: T): (T, T) {
+    return (id(x), id(x));
+}
+var p = pair("z");
+writeLn!(p.1)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "z" in stdout
+
+
+def test_optional_parameter():
+    code = """
+fnc or_else<T>(x: T?, d: T): T {
+    return unwrapOr!(x, d);
+}
+var n: numb? = undef;
+writeLn!(or_else(n, 4))
+writeLn!(or_else("s", "t"))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "4" in stdout
+    assert "s" in stdout
+
+
+def test_constraint_not_met():
+    code = """
+fnc max<T: numeric>(a: T, b: T): T {
+    if (a > b) {
+        return a;
+    }
+    return b;
+}
+writeLn!(max("a", "b"))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0042" in stdout
+
+
+def test_conflicting_arguments():
+    code = """
+fnc max<T: numeric>(a: T, b: T): T {
+    if (a > b) {
+        return a;
+    }
+    return b;
+}
+writeLn!(max(1, 2.5))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0004" in stdout
+
+
+def test_unknown_constraint():
+    code = """
+fnc show<T: text>(value: T) {
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0041" in stdout
+
+
+def test_cannot_infer():
+    code = """
+fnc id<T>(x: T): T {
+    return x;
+}
+writeLn!(id(undef))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0043" in stdout
+
+
+def test_body_checked_per_instance():
+    code = """
+fnc inc<T>(x: T): numb {
+    return x + 1;
+}
+writeLn!(inc(1))
+writeLn!(inc("a"))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0005" in stdout
+
+
+def test_printable_constraint_with_macros():
+    code = """
+fnc show<T: printable>(value: T) {
+    write!(value)
+    writeLn!(value)
+}
+show(false)
+show(2.5)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "falsefalse" in stdout
+    assert "2.52.5" in stdout
+
+
+def test_printable_constraint_rejects_array():
+    code = """
+fnc show<T: printable>(value: T) {
+    writeLn!(value)
+}
+show([1, 2])
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0042" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running Generics tests...")
+
+    tests = [
+        ("numeric max", test_numeric_max),
+        ("array element type", test_array_element_type),
+        ("printable constraint", test_printable_constraint),
+        ("inferred from closure", test_inferred_from_closure),
+        ("generic calls generic", test_generic_calls_generic),
+        ("optional parameter", test_optional_parameter),
+        ("constraint not met", test_constraint_not_met),
+        ("conflicting arguments", test_conflicting_arguments),
+        ("unknown constraint", test_unknown_constraint),
+        ("cannot infer", test_cannot_infer),
+        ("body checked per instance", test_body_checked_per_instance),
+        ("printable constraint with macros", test_printable_constraint_with_macros),
+        ("printable constraint rejects array", test_printable_constraint_rejects_array),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
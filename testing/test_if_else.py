@@ -25,7 +25,7 @@ def run_flare_code(code: str) -> tuple[int, str, str]:
         return build_proc.returncode, build_proc.stdout, build_proc.stderr
 
     run_proc = subprocess.run(
-        [COMPILER_PATH, "run", f"target/{OUTPUT_NAME}"], capture_output=True, text=True
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
     )
 
     return run_proc.returncode, run_proc.stdout, run_proc.stderr
@@ -259,6 +259,70 @@ else{
     assert "second else" not in stdout
 
 
+def test_else_if_chain():
+    code = """
+var x = 3;
+if(x == 1){
+    writeLn!("one")
+}
+else if(x == 2){
+    writeLn!("two")
+}
+else if(x == 3){
+    writeLn!("three")
+}
+else{
+    writeLn!("other")
+}
+writeLn!("after")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "one" not in stdout
+    assert "two" not in stdout
+    assert "three" in stdout
+    assert "other" not in stdout
+    assert "after" in stdout
+
+
+def test_else_if_falls_through_to_else():
+    code = """
+var x = 7;
+if(x < 0){
+    writeLn!("negative")
+}
+else if(x < 5){
+    writeLn!("small")
+}
+else{
+    writeLn!("big")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "negative" not in stdout
+    assert "small" not in stdout
+    assert "big" in stdout
+
+
+def test_else_if_without_else():
+    code = """
+var x = 1;
+if(x == 0){
+    writeLn!("zero")
+}
+else if(x == 2){
+    writeLn!("two")
+}
+writeLn!("done")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "zero" not in stdout
+    assert "two" not in stdout
+    assert "done" in stdout
+
+
 if __name__ == "__main__":
     setup()
     print("Running if-else tests...")
@@ -278,6 +342,9 @@ if __name__ == "__main__":
         ("if with variable assignment", test_if_with_variable_assignment),
         ("multiple if statements", test_multiple_if_statements),
         ("if after if else", test_if_after_if_else),
+        ("else if chain", test_else_if_chain),
+        ("else if falls through to else", test_else_if_falls_through_to_else),
+        ("else if without else", test_else_if_without_else),
     ]
 
     passed = 0
@@ -0,0 +1,245 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-loops"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_loop_break():
+    code = """
+var i = 0;
+loop {
+    i = i + 1;
+    if (i > 3) {
+        break;
+    }
+    writeLn!("iteration {i}")
+}
+writeLn!("done")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "iteration 3" in stdout
+    assert "iteration 4" not in stdout
+    assert "done" in stdout
+
+
+def test_while_continue():
+    code = """
+var i = 0;
+while (i < 5) {
+    i = i + 1;
+    if (i == 2) {
+        continue;
+    }
+    writeLn!("value {i}")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "value 1" in stdout
+    assert "value 2" not in stdout
+    assert "value 5" in stdout
+
+
+def test_labeled_break():
+    code = """
+var a = 0;
+'outer: loop {
+    a = a + 1;
+    while (true) {
+        if (a == 3) {
+            break 'outer;
+        }
+        continue 'outer;
+    }
+}
+writeLn!("done {a}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "done 3" in stdout
+
+
+def test_break_outside_loop():
+    code = """
+if (true) {
+    break;
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0015" in stdout
+
+
+def test_undefined_label():
+    code = """
+loop {
+    break 'missing;
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0016" in stdout
+
+
+def test_for_range():
+    code = """
+for i in 0..3 {
+    writeLn!("item {i}")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "item 0" in stdout
+    assert "item 2" in stdout
+    assert "item 3" not in stdout
+
+
+def test_for_inclusive_step():
+    code = """
+for i in 0..=10 step 5 {
+    write!("{i} ")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "0 5 10" in stdout
+
+
+def test_for_break_continue():
+    code = """
+for i in 0..10 {
+    if (i == 1) {
+        continue;
+    }
+    if (i == 3) {
+        break;
+    }
+    write!("{i} ")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "0 2 " in stdout
+    assert "0 1" not in stdout
+    assert "2 3" not in stdout
+
+
+def test_for_variable_scoped():
+    code = """
+for i in 0..3 {
+}
+writeLn!(i)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Undefined variable" in stdout
+
+
+def test_for_variable_keeps_outer_value():
+    code = """
+var i = 100;
+for i in 0..3 {
+    write!("{i} ")
+}
+writeLn!("after {i}")
+fnc inner() {
+    var j = 7;
+    for j in 0..2 {
+    }
+    writeLn!("inner {j}")
+}
+inner()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "0 1 2 after 100" in stdout
+    assert "inner 7" in stdout
+
+
+def test_for_constant_step_must_be_positive():
+    for step in ["0", "-2"]:
+        code = f"""
+for i in 0..3 step {step} {{
+}}
+"""
+        exit_code, stdout, stderr = run_flare_code(code)
+        assert exit_code != 0
+        assert "E0044" in stdout
+
+
+def test_for_runtime_step_is_checked():
+    code = """
+var s = 0;
+for i in 0..3 step s {
+    writeLn!("body")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "body" not in stdout
+    assert "step of a for loop must be greater than 0" in stderr
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running loop tests...")
+
+    tests = [
+        ("loop break", test_loop_break),
+        ("while continue", test_while_continue),
+        ("labeled break", test_labeled_break),
+        ("break outside loop", test_break_outside_loop),
+        ("undefined label", test_undefined_label),
+        ("for range", test_for_range),
+        ("for inclusive step", test_for_inclusive_step),
+        ("for break continue", test_for_break_continue),
+        ("for variable scoped", test_for_variable_scoped),
+        ("for variable keeps outer value", test_for_variable_keeps_outer_value),
+        ("for constant step must be positive", test_for_constant_step_must_be_positive),
+        ("for runtime step is checked", test_for_runtime_step_is_checked),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,174 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-maps"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_literal_lookup_and_insert():
+    code = """
+var ages: map<string, numb> = {"bob": 30, "al": 25};
+ages["cy"] = 40;
+ages["bob"] = 31;
+writeLn!("{ages["bob"]} {ages["cy"]} {len!(ages)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "31 40 3" in stdout
+
+
+def test_has_remove_and_keys():
+    code = """
+var m = {"b": 2, "a": 1, "c": 3};
+remove!(m, "c")
+writeLn!("{has!(m, "a")} {has!(m, "c")}")
+var ks = keys!(m);
+for i in 0..len!(ks) {
+    write!("{ks[i]}={m[ks[i]]} ")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true false" in stdout
+    assert "a=1 b=2 " in stdout
+
+
+def test_number_keys_are_sorted():
+    code = """
+var names = {3: "c", 1: "a", 2: "b"};
+var ks = keys!(names);
+writeLn!("{ks[0]}{ks[1]}{ks[2]} {names[2]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "123 b" in stdout
+
+
+def test_nested_maps_and_fields():
+    code = """
+struct Inventory { items: map<string, numb[]> }
+var inv = Inventory { items: {} };
+inv.items["apples"] = [1];
+push!(inv.items["apples"], 2)
+var nested: map<string, map<string, numb>> = {"a": {}};
+nested["a"]["b"] = 5;
+writeLn!("{len!(inv.items["apples"])} {nested["a"]["b"]}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2 5" in stdout
+
+
+def test_missing_key():
+    code = """
+var m = {"a": 1};
+writeLn!(m["b"])
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert 'Key "b" is not in the map' in stderr
+
+
+def test_invalid_key_type():
+    code = """var m: map<flt, numb>;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0035" in stdout
+
+
+def test_key_type_mismatch():
+    code = """
+var m = {"a": 1};
+writeLn!(m[0])
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_value_type_mismatch():
+    code = """
+var m: map<string, numb>;
+m["a"] = "one";
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_empty_map_needs_type():
+    code = """var m = {};"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0002" in stdout
+
+
+def test_map_macro_on_array():
+    code = """
+var a = [1];
+writeLn!(has!(a, 1))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0036" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running map tests...")
+
+    tests = [
+        ("literal lookup and insert", test_literal_lookup_and_insert),
+        ("has remove and keys", test_has_remove_and_keys),
+        ("number keys are sorted", test_number_keys_are_sorted),
+        ("nested maps and fields", test_nested_maps_and_fields),
+        ("missing key", test_missing_key),
+        ("invalid key type", test_invalid_key_type),
+        ("key type mismatch", test_key_type_mismatch),
+        ("value type mismatch", test_value_type_mismatch),
+        ("empty map needs type", test_empty_map_needs_type),
+        ("map macro on array", test_map_macro_on_array),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,133 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-match"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_match_literals_and_ranges():
+    code = """
+for i in 0..6 {
+    match i {
+        0 => writeLn!("zero"),
+        1..3 => writeLn!("small {i}"),
+        3..=4 => {
+            writeLn!("medium {i}")
+        }
+        _ => writeLn!("other {i}")
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "zero" in stdout
+    assert "small 2" in stdout
+    assert "medium 4" in stdout
+    assert "other 5" in stdout
+    assert "other 4" not in stdout
+
+
+def test_match_string():
+    code = """
+var cmd = "quit";
+match cmd {
+    "go" => writeLn!("going"),
+    "quit" => writeLn!("quitting"),
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "quitting" in stdout
+    assert "going" not in stdout
+
+
+def test_match_bool():
+    code = """
+var flag = false;
+match flag {
+    true => writeLn!("yes"),
+    false => writeLn!("no"),
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "no" in stdout
+    assert "yes" not in stdout
+
+
+def test_match_bool_non_exhaustive():
+    code = """
+match true {
+    true => writeLn!("yes"),
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0017" in stdout
+
+
+def test_match_pattern_type_mismatch():
+    code = """
+match 1 {
+    "one" => writeLn!("one"),
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running match tests...")
+
+    tests = [
+        ("match literals and ranges", test_match_literals_and_ranges),
+        ("match string", test_match_string),
+        ("match bool", test_match_bool),
+        ("match bool non exhaustive", test_match_bool_non_exhaustive),
+        ("match pattern type mismatch", test_match_pattern_type_mismatch),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
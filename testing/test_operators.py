@@ -0,0 +1,143 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-operators"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_equality():
+    code = """
+var a = 5;
+var s = "x";
+writeLn!("{a == 5} {a != 5} {s == "x"} {1 + 2 == 3}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true false true true" in stdout
+
+
+def test_comparison():
+    code = """
+var a = 5;
+writeLn!("{a <= 5} {a >= 6} {a < 5} {a > 4}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true false false true" in stdout
+
+
+def test_no_space_not_equal():
+    code = """
+var a = 5;
+if (a!=4) {
+    writeLn!("different")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "different" in stdout
+
+
+def test_equality_type_mismatch():
+    code = """var a = 1 == "x";"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Invalid binary operation" in stdout
+
+
+def test_logical_operators():
+    code = """
+var x = 5;
+if (x > 0 && x < 10) {
+    writeLn!("in range")
+}
+writeLn!("{true && false} {false || true} {!true} {not false and x == 5}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "in range" in stdout
+    assert "false true false true" in stdout
+
+
+def test_logical_non_bool():
+    code = """var a = 1 && true;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Invalid binary operation: &&" in stdout
+
+
+def test_unary_prefix():
+    code = """
+var x = -5;
+var y = 2.5;
+writeLn!("{-y} {+x} {-x * 2} {10 - -3} {!(x > 0)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "-2.5 -5 10 13 true" in stdout
+
+
+def test_negate_string():
+    code = """var a = -"x";"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running operator tests...")
+
+    tests = [
+        ("equality", test_equality),
+        ("comparison", test_comparison),
+        ("no space not equal", test_no_space_not_equal),
+        ("equality type mismatch", test_equality_type_mismatch),
+        ("logical operators", test_logical_operators),
+        ("logical non bool", test_logical_non_bool),
+        ("unary prefix", test_unary_prefix),
+        ("negate string", test_negate_string),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
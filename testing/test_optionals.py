@@ -0,0 +1,325 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-optionals"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_unwrap_or():
+    code = """
+var x: numb? = undef;
+var y: numb? = 5;
+writeLn!(unwrapOr!(x, 10))
+writeLn!(unwrapOr!(y, 10))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "10\n5" in stdout
+
+
+def test_declared_without_value_is_undef():
+    code = """
+var name: string?;
+if (name == undef) {
+    writeLn!("no name")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "no name" in stdout
+
+
+def test_narrowing_in_if():
+    code = """
+var y: numb? = 5;
+if (y != undef) {
+    writeLn!(y + 1)
+}
+var z: numb?;
+if (z == undef) {
+    writeLn!("z empty")
+} else {
+    writeLn!(z * 2)
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "6" in stdout
+    assert "z empty" in stdout
+
+
+def test_narrowing_in_logical_operators():
+    code = """
+var x: numb? = 4;
+var y: numb?;
+if (x != undef && x > 3) {
+    writeLn!("big")
+}
+if (y == undef || y > 3) {
+    writeLn!("empty or big")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "big" in stdout
+    assert "empty or big" in stdout
+
+
+def test_optional_return_value():
+    code = """
+fnc find(items: numb[], target: numb): numb? {
+    for i in 0..len!(items) {
+        if (items[i] == target) {
+            return i;
+        }
+    }
+    return undef;
+}
+var idx = find([4, 5, 6], 6);
+if (idx != undef) {
+    writeLn!("found at {idx}")
+}
+writeLn!(unwrapOr!(find([1], 9), -1))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "found at 2" in stdout
+    assert "-1" in stdout
+
+
+def test_narrowed_global_in_function():
+    code = """
+var limit: numb? = 3;
+fnc show() {
+    if (limit != undef) {
+        writeLn!("limit {limit}")
+    }
+}
+show()
+limit = undef;
+show()
+writeLn!("done")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert stdout.count("limit 3") == 1
+    assert "done" in stdout
+
+
+def test_array_of_optionals():
+    code = """
+var names = ["a", undef];
+writeLn!(unwrapOr!(names[1], "none"))
+writeLn!(unwrapOr!(names[0], "none"))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "none\na" in stdout
+
+
+def test_unchecked_use():
+    code = """
+var x: numb? = 1;
+writeLn!(x + 1)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0038" in stdout
+
+
+def test_unchecked_print():
+    code = """
+var x: numb? = 1;
+writeLn!(x)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0038" in stdout
+
+
+def test_no_narrowing_when_undef():
+    code = """
+var x: numb? = 1;
+if (x == undef) {
+    writeLn!(x)
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0038" in stdout
+
+
+def test_undef_needs_type():
+    code = """var x = undef;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0002" in stdout
+
+
+def test_optional_to_plain_variable():
+    code = """
+var x: numb? = 1;
+var y: numb = x;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_unwrap_or_default_type():
+    code = """
+var x: string? = "a";
+writeLn!(unwrapOr!(x, 1))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_no_narrowing_of_global_across_call():
+    code = """
+var x: numb? = 1;
+fnc clear() {
+    x = undef;
+}
+if (x != undef) {
+    clear()
+    writeLn!(x + 1)
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0038" in stdout
+
+
+def test_no_narrowing_of_global_in_function_defined_inside_check():
+    code = """
+var x: numb? = 1;
+if (x != undef) {
+    fnc next(): numb {
+        return x + 1;
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0038" in stdout
+
+
+def test_narrowing_of_local_across_call():
+    code = """
+fnc log(message: string) {
+    writeLn!(message)
+}
+fnc show(y: numb?) {
+    if (y != undef) {
+        log("set")
+        writeLn!(y + 1)
+    }
+}
+show(2)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "set\n3" in stdout
+
+
+def test_narrowed_variable_cannot_be_set_to_undef():
+    code = """
+var x: numb? = 1;
+if (x != undef) {
+    x = undef;
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0004" in stdout
+
+
+def test_declaration_without_value_needs_optional():
+    for declared in ["numb", "flt", "string", "bool"]:
+        exit_code, stdout, stderr = run_flare_code(f"var x: {declared};")
+        assert exit_code != 0
+        assert "E0039" in stdout, declared
+
+
+def test_declaration_without_value_of_collections():
+    code = """
+var name: string?;
+var names: string[];
+var ages: map<string, numb>;
+writeLn!("{name == undef} {len!(names)} {len!(ages)}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "true 0 0" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running optional tests...")
+
+    tests = [
+        ("unwrap or", test_unwrap_or),
+        ("declared without value is undef", test_declared_without_value_is_undef),
+        ("narrowing in if", test_narrowing_in_if),
+        ("narrowing in logical operators", test_narrowing_in_logical_operators),
+        ("optional return value", test_optional_return_value),
+        ("narrowed global in function", test_narrowed_global_in_function),
+        ("array of optionals", test_array_of_optionals),
+        ("unchecked use", test_unchecked_use),
+        ("unchecked print", test_unchecked_print),
+        ("no narrowing when undef", test_no_narrowing_when_undef),
+        ("undef needs type", test_undef_needs_type),
+        ("optional to plain variable", test_optional_to_plain_variable),
+        ("unwrap or default type", test_unwrap_or_default_type),
+        ("no narrowing of global across call", test_no_narrowing_of_global_across_call),
+        ("no narrowing of global in function defined inside check", test_no_narrowing_of_global_in_function_defined_inside_check),
+        ("narrowing of local across call", test_narrowing_of_local_across_call),
+        ("narrowed variable cannot be set to undef", test_narrowed_variable_cannot_be_set_to_undef),
+        ("declaration without value needs optional", test_declaration_without_value_needs_optional),
+        ("declaration without value of collections", test_declaration_without_value_of_collections),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,110 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-strings"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_escapes():
+    code = r"""writeLn!("a\tb \"q\" \\ \u{48}")"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert 'a\tb "q" \\ H' in stdout
+
+
+def test_invalid_escape():
+    code = r"""writeLn!("bad \q")"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Invalid escape sequence" in stdout
+
+
+def test_raw_string():
+    code = r"""writeLn!(r#"raw \n "quoted""#)"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert r'raw \n "quoted"' in stdout
+
+
+def test_multiline_string():
+    code = '''writeLn!("""
+first
+second""")'''
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "first\nsecond" in stdout
+
+
+def test_interpolation():
+    code = r"""
+var name = "Bob";
+var n = 41;
+writeLn!("Hello {name}, {n + 1} {n > 3} \{literal\}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "Hello Bob, 42 true {literal}" in stdout
+
+
+def test_empty_interpolation():
+    code = r"""writeLn!("oops {}")"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Empty interpolation" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running string tests...")
+
+    tests = [
+        ("escapes", test_escapes),
+        ("invalid escape", test_invalid_escape),
+        ("raw string", test_raw_string),
+        ("multiline string", test_multiline_string),
+        ("interpolation", test_interpolation),
+        ("empty interpolation", test_empty_interpolation),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
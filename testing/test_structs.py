@@ -0,0 +1,335 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-structs"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_struct_fields():
+    code = """
+struct Point { x: flt, y: flt }
+var p = Point { x: 1.0, y: 2.0 };
+writeLn!(p.x)
+p.x = 3.5;
+writeLn!("{p.x} {p.y}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "1\n" in stdout
+    assert "3.5 2" in stdout
+
+
+def test_nested_struct_assignment():
+    code = """
+struct Point { x: flt, y: flt }
+struct Line { from: Point, to: Point }
+var l = Line { to: Point { x: 5.0, y: 6.0 }, from: Point { x: 0.0, y: 0.0 } };
+l.to.y = 9.5;
+writeLn!("{l.from.x} {l.to.x} {l.to.y}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "0 5 9.5" in stdout
+
+
+def test_struct_in_functions():
+    code = """
+fnc make(x: flt, y: flt): Point {
+    return Point { x: x, y: y };
+}
+fnc len2(p: Point): flt {
+    return p.x * p.x + p.y * p.y;
+}
+struct Point { x: flt, y: flt }
+writeLn!(len2(make(3.0, 4.0)))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "25" in stdout
+
+
+def test_struct_needs_value():
+    code = """
+struct Named { name: string, count: numb }
+var n: Named;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0039" in stdout
+
+
+def test_unknown_field():
+    code = """
+struct Point { x: flt }
+var p = Point { x: 1.0 };
+writeLn!(p.z)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0024" in stdout
+
+
+def test_missing_field():
+    code = """
+struct Point { x: flt, y: flt }
+var p = Point { x: 1.0 };
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0025" in stdout
+
+
+def test_duplicate_field():
+    code = """
+struct Point { x: flt }
+var p = Point { x: 1.0, x: 2.0 };
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0026" in stdout
+
+
+def test_duplicate_struct():
+    code = """
+struct Point { x: flt }
+struct Point { y: flt }
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0023" in stdout
+
+
+def test_field_type_mismatch():
+    code = """
+struct Point { x: flt }
+var p = Point { x: 1.0 };
+p.x = "one";
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_const_struct_field():
+    code = """
+struct Point { x: flt }
+const p = Point { x: 1.0 };
+p.x = 2.0;
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0009" in stdout
+
+
+def test_methods():
+    code = """
+struct Point { x: flt, y: flt }
+impl Point {
+    fnc new(x: flt, y: flt): Point {
+        return Point { x: x, y: y };
+    }
+    fnc len2(self): flt {
+        return self.x * self.x + self.y * self.y;
+    }
+    fnc scaled(self, by: flt = 2.0): Point {
+        return Point.new(self.x * by, self.y * by);
+    }
+}
+var p = Point.new(3.0, 4.0);
+writeLn!("len2 {p.len2()}")
+writeLn!("scaled {p.scaled().x} {p.scaled(by = 3.0).scaled().y}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "len2 25" in stdout
+    assert "scaled 6 24" in stdout
+
+
+def test_method_before_impl():
+    code = """
+var c = Counter.new();
+writeLn!(c.next())
+struct Counter { count: numb }
+impl Counter {
+    fnc new(): Counter {
+        return Counter { count: 41 };
+    }
+    fnc next(self): numb {
+        return self.count + 1;
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "42" in stdout
+
+
+def test_unknown_method():
+    code = """
+struct Point { x: flt }
+var p = Point { x: 1.0 };
+p.length()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0027" in stdout
+
+
+def test_method_called_on_type():
+    code = """
+struct Point { x: flt }
+impl Point {
+    fnc get(self): flt {
+        return self.x;
+    }
+}
+Point.get()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0028" in stdout
+
+
+def test_static_called_on_value():
+    code = """
+struct Point { x: flt }
+impl Point {
+    fnc origin(): Point {
+        return Point { x: 0.0 };
+    }
+}
+var p = Point { x: 1.0 };
+p.origin()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0029" in stdout
+
+
+def test_too_many_fields_for_bytecode():
+    fields = ", ".join(f"f{i}: numb" for i in range(256))
+    values = ", ".join(f"f{i}: 1" for i in range(256))
+    code = f"""
+struct Wide {{ {fields} }}
+var w = Wide {{ {values} }};
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many struct fields (256)" in stdout
+
+
+def test_assign_to_self_field():
+    code = """
+struct Counter { count: numb }
+impl Counter {
+    fnc increment(self) {
+        self.count = self.count + 1;
+    }
+}
+var c = Counter { count: 0 };
+c.increment()
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0045" in stdout
+
+
+def test_assign_to_self():
+    code = """
+struct Counter { count: numb }
+impl Counter {
+    fnc reset(self) {
+        self = Counter { count: 0 };
+    }
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert "E0045" in stdout
+
+
+def test_method_returns_changed_copy():
+    code = """
+struct Counter { count: numb }
+impl Counter {
+    fnc incremented(self): Counter {
+        var next = self;
+        next.count = next.count + 1;
+        return next;
+    }
+}
+var c = Counter { count: 0 };
+c = c.incremented().incremented();
+writeLn!(c.count)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running struct tests...")
+
+    tests = [
+        ("struct fields", test_struct_fields),
+        ("nested struct assignment", test_nested_struct_assignment),
+        ("struct in functions", test_struct_in_functions),
+        ("struct needs value", test_struct_needs_value),
+        ("unknown field", test_unknown_field),
+        ("missing field", test_missing_field),
+        ("duplicate field", test_duplicate_field),
+        ("duplicate struct", test_duplicate_struct),
+        ("field type mismatch", test_field_type_mismatch),
+        ("const struct field", test_const_struct_field),
+        ("methods", test_methods),
+        ("method before impl", test_method_before_impl),
+        ("unknown method", test_unknown_method),
+        ("method called on type", test_method_called_on_type),
+        ("static called on value", test_static_called_on_value),
+        ("too many fields for bytecode", test_too_many_fields_for_bytecode),
+        ("assign to self field", test_assign_to_self_field),
+        ("assign to self", test_assign_to_self),
+        ("method returns changed copy", test_method_returns_changed_copy),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
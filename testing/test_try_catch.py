@@ -0,0 +1,223 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-try-catch"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_throw_and_catch():
+    code = """
+try {
+    writeLn!("before")
+    throw "oops";
+    writeLn!("not reached")
+} catch (e) {
+    writeLn!("caught {e}")
+}
+writeLn!("after")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "before\ncaught oops\nafter" in stdout
+    assert "not reached" not in stdout
+
+
+def test_propagation_out_of_functions():
+    code = """
+fnc divide(a: numb, b: numb): numb {
+    if (b == 0) {
+        throw "division by zero";
+    }
+    return a / b;
+}
+fnc half_of_ratio(a: numb, b: numb): numb {
+    return divide(a, b) / 2;
+}
+try {
+    writeLn!(half_of_ratio(8, 2))
+    writeLn!(half_of_ratio(1, 0))
+} catch (e) {
+    writeLn!("failed: {e}")
+}
+writeLn!(divide(9, 3))
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2\nfailed: division by zero\n3" in stdout
+
+
+def test_catch_runtime_errors():
+    code = """
+var arr = [1, 2];
+try {
+    writeLn!(arr[5])
+} catch (e) {
+    writeLn!("index: {e}")
+}
+var zero = 0;
+try {
+    writeLn!(1 / zero)
+} catch {
+    writeLn!("division failed")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "index: Index out of bounds" in stdout
+    assert "division failed" in stdout
+
+
+def test_nested_and_rethrow():
+    code = """
+try {
+    try {
+        throw "inner";
+    } catch (e) {
+        writeLn!("inner caught {e}")
+        throw "again {e}";
+    }
+} catch (e) {
+    writeLn!("outer caught {e}")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "inner caught inner" in stdout
+    assert "outer caught again inner" in stdout
+
+
+def test_break_and_continue_leave_try():
+    code = """
+for i in 0..5 {
+    try {
+        if (i == 1) {
+            continue;
+        }
+        if (i == 3) {
+            break;
+        }
+        write!("{i} ")
+    } catch {
+    }
+}
+throw "uncaught";
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "0 2 " in stdout
+    assert "uncaught" in stderr
+
+
+def test_return_from_try():
+    code = """
+fnc first_positive(items: numb[]): numb {
+    try {
+        for i in 0..len!(items) {
+            if (items[i] > 0) {
+                return items[i];
+            }
+        }
+    } catch {
+    }
+    throw "none positive";
+}
+writeLn!(first_positive([-1, 4]))
+try {
+    first_positive([-1])
+} catch (e) {
+    writeLn!("error: {e}")
+}
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "4\nerror: none positive" in stdout
+
+
+def test_uncaught_error():
+    code = """
+writeLn!("start")
+throw "something broke";
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "something broke" in stderr
+
+
+def test_throw_needs_string():
+    code = """throw 5;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_error_variable_scope():
+    code = """
+try {
+    throw "x";
+} catch (e) {
+}
+writeLn!(e)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0006" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running try/catch tests...")
+
+    tests = [
+        ("throw and catch", test_throw_and_catch),
+        ("propagation out of functions", test_propagation_out_of_functions),
+        ("catch runtime errors", test_catch_runtime_errors),
+        ("nested and rethrow", test_nested_and_rethrow),
+        ("break and continue leave try", test_break_and_continue_leave_try),
+        ("return from try", test_return_from_try),
+        ("uncaught error", test_uncaught_error),
+        ("throw needs string", test_throw_needs_string),
+        ("error variable scope", test_error_variable_scope),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")
@@ -0,0 +1,177 @@
+import os
+import subprocess
+
+COMPILER_PATH = "target/debug/flarec"
+TEST_DIR = "testing/temp"
+OUTPUT_NAME = "test-tuples"
+
+
+def setup():
+    os.makedirs(TEST_DIR, exist_ok=True)
+
+
+def run_flare_code(code: str) -> tuple[int, str, str]:
+    """Compile and run Flare code, return (exit_code, stdout, stderr)"""
+    test_file = os.path.join(TEST_DIR, "test.flare")
+
+    with open(test_file, "w") as f:
+        f.write(code)
+
+    build_proc = subprocess.run(
+        [COMPILER_PATH, "build", test_file, OUTPUT_NAME], capture_output=True, text=True
+    )
+
+    if build_proc.returncode != 0:
+        return build_proc.returncode, build_proc.stdout, build_proc.stderr
+
+    run_proc = subprocess.run(
+        [COMPILER_PATH, "run", f"out/{OUTPUT_NAME}"], capture_output=True, text=True
+    )
+
+    return run_proc.returncode, run_proc.stdout, run_proc.stderr
+
+
+def test_tuple_return_and_destructuring():
+    code = """
+fnc min_max(xs: numb[]): (numb, numb) {
+    var lo = xs[0];
+    var hi = xs[0];
+    for i in 0..len!(xs) {
+        if (xs[i] < lo) { lo = xs[i]; }
+        if (xs[i] > hi) { hi = xs[i]; }
+    }
+    return (lo, hi);
+}
+var (lo, hi) = min_max([4, 9, 2]);
+writeLn!("{lo}..{hi}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2..9" in stdout
+
+
+def test_element_access_and_assignment():
+    code = """
+var t: (numb, string) = (1, "one");
+t.0 = 5;
+writeLn!("{t.0} {t.1}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "5 one" in stdout
+
+
+def test_nested_tuples():
+    code = """
+var nested = ((1, 2), "x");
+var ((a, b), _) = nested;
+writeLn!("{nested.0.1} {a}{b} {nested.1}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "2 12 x" in stdout
+
+
+def test_tuples_in_arrays():
+    code = """
+var pairs: (string, numb)[] = [("a", 1)];
+push!(pairs, ("b", 2))
+writeLn!("{pairs[1].0}{pairs[1].1}")
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "b2" in stdout
+
+
+def test_tuple_needs_value():
+    code = """var d: (numb, bool);"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0039" in stdout
+
+
+def test_float_after_tuple_access():
+    code = """
+var t = (1.5, 2);
+writeLn!(t.0 + 0.25)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code == 0
+    assert "1.75" in stdout
+
+
+def test_destructuring_wrong_length():
+    code = """var (a, b) = (1, 2, 3);"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0037" in stdout
+
+
+def test_destructuring_non_tuple():
+    code = """var (a, b) = 5;"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0037" in stdout
+
+
+def test_index_out_of_range():
+    code = """
+var t = (1, 2);
+writeLn!(t.2)
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "E0024" in stdout
+
+
+def test_element_type_mismatch():
+    code = """var t: (numb, string) = ("a", 1);"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Type mismatch" in stdout
+
+
+def test_too_many_elements_for_bytecode():
+    values = ", ".join("1" for _ in range(256))
+    code = f"""
+var t = ({values});
+"""
+    exit_code, stdout, stderr = run_flare_code(code)
+    assert exit_code != 0
+    assert "Too many tuple elements (256)" in stdout
+
+
+if __name__ == "__main__":
+    setup()
+    print("Running tuple tests...")
+
+    tests = [
+        ("tuple return and destructuring", test_tuple_return_and_destructuring),
+        ("element access and assignment", test_element_access_and_assignment),
+        ("nested tuples", test_nested_tuples),
+        ("tuples in arrays", test_tuples_in_arrays),
+        ("tuple needs value", test_tuple_needs_value),
+        ("float after tuple access", test_float_after_tuple_access),
+        ("destructuring wrong length", test_destructuring_wrong_length),
+        ("destructuring non tuple", test_destructuring_non_tuple),
+        ("index out of range", test_index_out_of_range),
+        ("element type mismatch", test_element_type_mismatch),
+        ("too many elements for bytecode", test_too_many_elements_for_bytecode),
+    ]
+
+    passed = 0
+    failed = 0
+
+    for name, test_func in tests:
+        try:
+            test_func()
+            print(f"✓ {name}")
+            passed += 1
+        except AssertionError as e:
+            print(f"✗ {name}: {e}")
+            failed += 1
+        except Exception as e:
+            print(f"✗ {name}: ERROR - {e}")
+            failed += 1
+
+    print(f"\n{passed} passed, {failed} failed")